@@ -4,7 +4,7 @@ use criterion::{
 };
 use std::time::Duration;
 
-use vecshard::{ShardExt, VecShard};
+use vecshard::{MergePolicy, ShardExt, VecShard};
 
 const SIZES: [usize; 9] = [
     0x10, 0x40, 0x100, 0x400, 0x1000, 0x4000, 0x1_0000, 0x4_0000, 0x10_0000,
@@ -36,6 +36,31 @@ fn split(c: &mut Criterion) {
     );
 }
 
+fn split_unchecked(c: &mut Criterion) {
+    c.bench(
+        "split_unchecked",
+        ParameterizedBenchmark::new(
+            "checked",
+            |b, &&size| {
+                b.iter_batched(
+                    || VecShard::from(vec![0u8; size]),
+                    |shard| shard.split_inplace_at(black_box(size / 2)),
+                    BatchSize::SmallInput,
+                )
+            },
+            &[1, 2, 4, 8],
+        )
+        .with_function("unchecked", |b, &&size| {
+            b.iter_batched(
+                || VecShard::from(vec![0u8; size]),
+                |shard| unsafe { shard.split_inplace_at_unchecked(black_box(size / 2)) },
+                BatchSize::SmallInput,
+            )
+        })
+        .sample_size(1000),
+    );
+}
+
 fn index(c: &mut Criterion) {
     c.bench(
         "index",
@@ -92,6 +117,100 @@ fn merge(c: &mut Criterion) {
     );
 }
 
+fn flatten(c: &mut Criterion) {
+    c.bench(
+        "flatten",
+        ParameterizedBenchmark::new(
+            "fold_merge_adjacent",
+            |b, &&size| {
+                b.iter_batched(
+                    || VecShard::from(vec![0u8; size]).into_shard_chunks(8).collect::<Vec<_>>(),
+                    |chunks| chunks.into_iter().fold(VecShard::new(), VecShard::merge),
+                    BatchSize::LargeInput,
+                )
+            },
+            &SIZES,
+        )
+        .with_function("flatten_adjacent", |b, &&size| {
+            b.iter_batched(
+                || VecShard::from(vec![0u8; size]).into_shard_chunks(8).collect::<Vec<_>>(),
+                VecShard::flatten_shards,
+                BatchSize::LargeInput,
+            )
+        })
+        .with_function("fold_merge_scattered", |b, &&size| {
+            b.iter_batched(
+                || (0..size / 8).map(|_| VecShard::from(vec![0u8; 8])).collect::<Vec<_>>(),
+                |chunks| chunks.into_iter().fold(VecShard::new(), VecShard::merge),
+                BatchSize::LargeInput,
+            )
+        })
+        .with_function("flatten_scattered", |b, &&size| {
+            b.iter_batched(
+                || (0..size / 8).map(|_| VecShard::from(vec![0u8; 8])).collect::<Vec<_>>(),
+                VecShard::flatten_shards,
+                BatchSize::LargeInput,
+            )
+        })
+        .plot_config(PlotConfiguration::default().summary_scale(Logarithmic)),
+    );
+}
+
+// b and c at the front, a at the back, with a gap in between
+fn bc_a(size: usize) -> (VecShard<u8>, VecShard<u8>, VecShard<u8>) {
+    let (b, rest) = vec![0u8; size].split_inplace_at(size / 4);
+    let (c, rest) = rest.split_inplace_at(size / 4);
+    let (_gap, a) = rest.split_inplace_at(size / 4);
+    (a, b, c)
+}
+
+fn scattered3(size: usize) -> (VecShard<u8>, VecShard<u8>, VecShard<u8>) {
+    (
+        VecShard::from(vec![0u8; size / 4]),
+        VecShard::from(vec![0u8; size / 4]),
+        VecShard::from(vec![0u8; size / 4]),
+    )
+}
+
+fn merge3(c: &mut Criterion) {
+    c.bench(
+        "merge3",
+        ParameterizedBenchmark::new(
+            "sequential_bc-a",
+            |b, &&size| {
+                b.iter_batched(
+                    || bc_a(size),
+                    |(a, b, c)| VecShard::merge(VecShard::merge(a, b), c),
+                    BatchSize::LargeInput,
+                )
+            },
+            &SIZES,
+        )
+        .with_function("merge3_bc-a", |b, &&size| {
+            b.iter_batched(
+                || bc_a(size),
+                |(a, b, c)| VecShard::merge3(a, b, c),
+                BatchSize::LargeInput,
+            )
+        })
+        .with_function("sequential_scattered", |b, &&size| {
+            b.iter_batched(
+                || scattered3(size),
+                |(a, b, c)| VecShard::merge(VecShard::merge(a, b), c),
+                BatchSize::LargeInput,
+            )
+        })
+        .with_function("merge3_scattered", |b, &&size| {
+            b.iter_batched(
+                || scattered3(size),
+                |(a, b, c)| VecShard::merge3(a, b, c),
+                BatchSize::LargeInput,
+            )
+        })
+        .plot_config(PlotConfiguration::default().summary_scale(Logarithmic)),
+    );
+}
+
 fn iterate(c: &mut Criterion) {
     c.bench(
         "iterate",
@@ -154,6 +273,58 @@ fn iterate(c: &mut Criterion) {
     );
 }
 
+fn map_collect(c: &mut Criterion) {
+    c.bench(
+        "map_collect",
+        ParameterizedBenchmark::new(
+            "shard_drain",
+            |b, &&size| {
+                b.iter_batched(
+                    || VecShard::from(vec![0u32; size]),
+                    |shard| shard.map(|x| x + 1).collect::<Vec<_>>(),
+                    BatchSize::LargeInput,
+                )
+            },
+            &SIZES,
+        )
+        .with_function("shard_into_vec_iter", |b, &&size| {
+            b.iter_batched(
+                || VecShard::from(vec![0u32; size]),
+                |shard| shard.into_vec_iter().map(|x| x + 1).collect::<Vec<_>>(),
+                BatchSize::LargeInput,
+            )
+        })
+        .plot_config(PlotConfiguration::default().summary_scale(Logarithmic)),
+    );
+}
+
+fn concat_text(c: &mut Criterion) {
+    c.bench(
+        "concat_text",
+        ParameterizedBenchmark::new(
+            "string_push_str",
+            |b, &&size| {
+                let text = "a".repeat(size);
+                b.iter_batched(
+                    || String::from(&text[..size / 2]),
+                    |mut left| left.push_str(&text[size / 2..]),
+                    BatchSize::LargeInput,
+                )
+            },
+            &SIZES,
+        )
+        .with_function("shard_merge_inplace", |b, &&size| {
+            b.iter_batched(
+                || VecShard::from("a".repeat(size)).split_inplace_at(size / 2),
+                |(left, right)| VecShard::merge_inplace(left, right).unwrap(),
+                BatchSize::LargeInput,
+            )
+        })
+        .sample_size(1000)
+        .plot_config(PlotConfiguration::default().summary_scale(Logarithmic)),
+    );
+}
+
 fn merge_patterns(c: &mut Criterion) {
     c.bench(
         "merge_patterns",
@@ -350,6 +521,101 @@ fn merge_patterns(c: &mut Criterion) {
     );
 }
 
-criterion_group!(vs_vec, split, index, merge, iterate);
-criterion_group!(merges, merge_patterns);
-criterion_main!(vs_vec, merges);
+fn merge_policy(c: &mut Criterion) {
+    c.bench(
+        "merge_policy",
+        ParameterizedBenchmark::new(
+            "rlll_move",
+            |b, &&size| {
+                b.iter_batched(
+                    || vec![0u8; size].split_inplace_at(size / 4),
+                    |(left, right)| VecShard::merge(right, left),
+                    BatchSize::LargeInput,
+                )
+            },
+            &SIZES,
+        )
+        .with_function("rlll_allocate", |b, &&size| {
+            b.iter_batched(
+                || vec![0u8; size].split_inplace_at(size / 4),
+                |(left, right)| {
+                    let policy = MergePolicy { max_move_bytes: 0 };
+                    VecShard::merge_with_policy(right, left, policy)
+                },
+                BatchSize::LargeInput,
+            )
+        })
+        .warm_up_time(Duration::from_secs(1))
+        .sample_size(1000)
+        .plot_config(PlotConfiguration::default().summary_scale(Logarithmic)),
+    );
+}
+
+fn join_into_vec(c: &mut Criterion) {
+    c.bench(
+        "join_into_vec",
+        ParameterizedBenchmark::new(
+            "merge_then_into_r-ll",
+            |b, &&size| {
+                b.iter_batched(
+                    || {
+                        let (right, rest) = vec![0u8; size].split_inplace_at(size / 4);
+                        (rest.split_inplace_at(size / 4).1, right)
+                    },
+                    |(left, right)| Vec::from(VecShard::merge(left, right)),
+                    BatchSize::LargeInput,
+                )
+            },
+            &SIZES,
+        )
+        .with_function("join_r-ll", |b, &&size| {
+            b.iter_batched(
+                || {
+                    let (right, rest) = vec![0u8; size].split_inplace_at(size / 4);
+                    (rest.split_inplace_at(size / 4).1, right)
+                },
+                |(left, right)| VecShard::join_into_vec(left, right),
+                BatchSize::LargeInput,
+            )
+        })
+        .with_function("merge_then_into_--lr", |b, &&size| {
+            b.iter_batched(
+                || {
+                    vec![0u8; size]
+                        .split_inplace_at(size / 2)
+                        .1
+                        .split_inplace_at(size / 4)
+                },
+                |(left, right)| Vec::from(VecShard::merge(left, right)),
+                BatchSize::LargeInput,
+            )
+        })
+        .with_function("join_--lr", |b, &&size| {
+            b.iter_batched(
+                || {
+                    vec![0u8; size]
+                        .split_inplace_at(size / 2)
+                        .1
+                        .split_inplace_at(size / 4)
+                },
+                |(left, right)| VecShard::join_into_vec(left, right),
+                BatchSize::LargeInput,
+            )
+        })
+        .warm_up_time(Duration::from_secs(1))
+        .sample_size(1000)
+        .plot_config(PlotConfiguration::default().summary_scale(Logarithmic)),
+    );
+}
+
+criterion_group!(vs_vec, split, index, merge, iterate, concat_text);
+criterion_group!(shards, split_unchecked, map_collect);
+criterion_group!(
+    merges,
+    merge_patterns,
+    flatten,
+    merge3,
+    merge_policy,
+    join_into_vec
+);
+criterion_main!(vs_vec, shards, merges);
@@ -0,0 +1,46 @@
+use criterion::{
+    criterion_group, criterion_main, AxisScale::Logarithmic, BatchSize, Criterion,
+    ParameterizedBenchmark, PlotConfiguration,
+};
+use rayon::slice::ParallelSliceMut;
+
+use vecshard::VecShard;
+
+const SIZES: [usize; 4] = [0x1000, 0x1_0000, 0x10_0000, 0x40_0000];
+
+fn scrambled(size: usize) -> Vec<u64> {
+    (0..size as u64)
+        .map(|i| i.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+        .collect()
+}
+
+fn par_sort(c: &mut Criterion) {
+    c.bench(
+        "par_sort",
+        ParameterizedBenchmark::new(
+            "rayon",
+            |b, &&size| {
+                b.iter_batched(
+                    || scrambled(size),
+                    |mut vec| {
+                        vec.par_sort();
+                        vec
+                    },
+                    BatchSize::LargeInput,
+                )
+            },
+            &SIZES,
+        )
+        .with_function("shard", |b, &&size| {
+            b.iter_batched(
+                || VecShard::from(scrambled(size)),
+                |shard| shard.par_sort(),
+                BatchSize::LargeInput,
+            )
+        })
+        .plot_config(PlotConfiguration::default().summary_scale(Logarithmic)),
+    );
+}
+
+criterion_group!(benches, par_sort);
+criterion_main!(benches);
@@ -0,0 +1,101 @@
+use crate::VecShard;
+use std::{borrow::Borrow, fmt, ops::Deref};
+
+/// Either a borrowed slice or an owned shard.
+///
+/// This is like [`Cow`](std::borrow::Cow), but with a [`VecShard`] as the owned variant,
+/// so a function that only sometimes has to build new data can return a borrow of its input
+/// the rest of the time, without allocating anything.
+///
+/// ```
+/// # use vecshard::{ShardCow, VecShard};
+/// fn without_zeroes(data: &[i32]) -> ShardCow<'_, i32> {
+///     if data.contains(&0) {
+///         ShardCow::Owned(VecShard::from(data.iter().copied().filter(|&x| x != 0).collect::<Vec<_>>()))
+///     } else {
+///         ShardCow::Borrowed(data)
+///     }
+/// }
+///
+/// assert_eq!(*without_zeroes(&[1, 0, 2]), [1, 2]);
+/// assert!(matches!(without_zeroes(&[1, 2]), ShardCow::Borrowed(_)));
+/// ```
+pub enum ShardCow<'a, T> {
+    Borrowed(&'a [T]),
+    Owned(VecShard<T>),
+}
+
+impl<'a, T> ShardCow<'a, T> {
+    /// Get an owned shard, cloning the elements if they're borrowed.
+    pub fn into_owned(self) -> VecShard<T>
+    where
+        T: Clone,
+    {
+        match self {
+            ShardCow::Borrowed(slice) => VecShard::from(slice.to_vec()),
+            ShardCow::Owned(shard) => shard,
+        }
+    }
+
+    /// Whether this holds a borrowed slice.
+    pub fn is_borrowed(&self) -> bool {
+        match self {
+            ShardCow::Borrowed(_) => true,
+            ShardCow::Owned(_) => false,
+        }
+    }
+
+    /// Whether this holds an owned shard.
+    pub fn is_owned(&self) -> bool {
+        !self.is_borrowed()
+    }
+}
+
+impl<'a, T> From<&'a [T]> for ShardCow<'a, T> {
+    fn from(slice: &'a [T]) -> Self {
+        ShardCow::Borrowed(slice)
+    }
+}
+
+impl<'a, T> From<VecShard<T>> for ShardCow<'a, T> {
+    fn from(shard: VecShard<T>) -> Self {
+        ShardCow::Owned(shard)
+    }
+}
+
+impl<'a, T> Deref for ShardCow<'a, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match self {
+            ShardCow::Borrowed(slice) => slice,
+            ShardCow::Owned(shard) => shard,
+        }
+    }
+}
+
+impl<'a, T> AsRef<[T]> for ShardCow<'a, T> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<'a, T> Borrow<[T]> for ShardCow<'a, T> {
+    fn borrow(&self) -> &[T] {
+        self
+    }
+}
+
+impl<'a, T: PartialEq> PartialEq for ShardCow<'a, T> {
+    fn eq(&self, rhs: &Self) -> bool {
+        **self == **rhs
+    }
+}
+
+impl<'a, T: Eq> Eq for ShardCow<'a, T> {}
+
+impl<'a, T: fmt::Debug> fmt::Debug for ShardCow<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
@@ -1,6 +1,7 @@
 use crate::VecShard;
 use std::error::Error;
 use std::fmt::{self, Debug, Display};
+use std::str::Utf8Error;
 
 /// A generic merge error.
 ///
@@ -12,6 +13,15 @@ pub struct CantMerge<T, E> {
     pub reason: E,
 }
 
+/// A conversion error.
+///
+/// Like [`CantMerge`], this hands you back the shard you tried to convert.
+#[derive(Debug)]
+pub struct ConversionError<T> {
+    pub shard: VecShard<T>,
+    pub reason: ConversionFailure,
+}
+
 /// A reason why an in-place merge was unsuccesful.
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum WouldMove {
@@ -27,6 +37,17 @@ pub enum WouldAlloc {
     OtherShardsLeft,
 }
 
+/// A reason why a conversion was unsuccesful.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ConversionFailure {
+    /// The shard isn't alone in its allocation, so its elements would have to be copied into a new one.
+    WouldAllocate,
+    /// The shard doesn't have the number of elements the target type needs.
+    WrongLength { expected: usize, actual: usize },
+    /// The shard's bytes aren't valid UTF-8.
+    InvalidUtf8(Utf8Error),
+}
+
 impl Display for WouldMove {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use WouldMove::*;
@@ -56,6 +77,17 @@ impl Display for WouldAlloc {
     }
 }
 
+impl Display for ConversionFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ConversionFailure::*;
+        match self {
+            WouldAllocate => write!(f, "the shard isn't the only one left in its allocation, so it would have to be copied."),
+            WrongLength { expected, actual } => write!(f, "the shard has {} elements instead of {}.", actual, expected),
+            InvalidUtf8(err) => write!(f, "the shard isn't valid UTF-8: {}.", err),
+        }
+    }
+}
+
 impl<T, R: Display> Display for CantMerge<T, R> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "Can't perform quick merge because {}", self.reason)
@@ -63,3 +95,11 @@ impl<T, R: Display> Display for CantMerge<T, R> {
 }
 
 impl<T: Debug, R: Debug + Display> Error for CantMerge<T, R> {}
+
+impl<T> Display for ConversionError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Can't convert shard because {}", self.reason)
+    }
+}
+
+impl<T: Debug> Error for ConversionError<T> {}
@@ -0,0 +1,279 @@
+//! Iterators over shards.
+
+use crate::VecShard;
+use std::{iter::FusedIterator, mem, ops::Deref, ptr};
+
+/// An iterator that drops the elements of a shard in batches.
+///
+/// This is created by [`VecShard::drop_in_batches`](crate::VecShard::drop_in_batches).
+#[derive(Debug)]
+pub struct DropBatches<T> {
+    pub(crate) shard: VecShard<T>,
+    pub(crate) batch: usize,
+}
+
+impl<T> Iterator for DropBatches<T> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let n = self.batch.min(self.shard.len);
+        if n > 0 {
+            self.shard.skip_shard(n);
+            Some(n)
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let batches = self.shard.len.div_ceil(self.batch);
+        (batches, Some(batches))
+    }
+}
+
+impl<T> ExactSizeIterator for DropBatches<T> {}
+
+impl<T> FusedIterator for DropBatches<T> {}
+
+/// An iterator over owned chunks of a shard.
+///
+/// This is created by [`VecShard::into_shard_chunks`](crate::VecShard::into_shard_chunks).
+#[derive(Debug)]
+pub struct ShardChunks<T> {
+    pub(crate) shard: VecShard<T>,
+    pub(crate) size: usize,
+}
+
+impl<T> Iterator for ShardChunks<T> {
+    type Item = VecShard<T>;
+
+    fn next(&mut self) -> Option<VecShard<T>> {
+        if self.shard.len > 0 {
+            Some(self.shard.take_shard(self.size))
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let chunks = self.shard.len.div_ceil(self.size);
+        (chunks, Some(chunks))
+    }
+}
+
+impl<T> ExactSizeIterator for ShardChunks<T> {}
+
+impl<T> FusedIterator for ShardChunks<T> {}
+
+/// An iterator over the length-prefixed frames in a byte shard.
+///
+/// This is created by [`VecShard::frames`](crate::VecShard::frames).
+#[derive(Debug)]
+pub struct Frames<F> {
+    pub(crate) shard: VecShard<u8>,
+    pub(crate) header_len: usize,
+    pub(crate) read_len: F,
+}
+
+impl<F> Frames<F> {
+    /// The bytes that haven't been split off as frames (yet).
+    pub fn remainder(&self) -> &[u8] {
+        &self.shard
+    }
+
+    /// Stop iterating and get the remaining bytes back, e.g. to add more to them.
+    pub fn into_remainder(self) -> VecShard<u8> {
+        self.shard
+    }
+}
+
+impl<F: Fn(&[u8]) -> usize> Iterator for Frames<F> {
+    type Item = VecShard<u8>;
+
+    fn next(&mut self) -> Option<VecShard<u8>> {
+        let frame_len = self.shard.frame_len(self.header_len, &self.read_len)?;
+        Some(self.shard.take_shard(frame_len))
+    }
+}
+
+/// A draining iterator over a shard that moves the remaining elements
+/// into a smaller allocation once most of them are gone.
+///
+/// This is created by [`VecShard::auto_shrink`](crate::VecShard::auto_shrink).
+#[derive(Debug)]
+pub struct AutoShrink<T> {
+    pub(crate) shard: VecShard<T>,
+    pub(crate) threshold: f32,
+}
+
+impl<T> AutoShrink<T> {
+    /// Get the shard back.
+    pub fn into_inner(self) -> VecShard<T> {
+        self.shard
+    }
+
+    pub(crate) fn maybe_shrink(&mut self) {
+        // ZSTs don't take up any memory, so there's nothing to release
+        if mem::size_of::<T>() == 0 {
+            return;
+        }
+        if (self.shard.len as f32) < self.threshold * self.shard.dropper.capacity as f32 {
+            self.shard.shrink_to_fit();
+        }
+    }
+}
+
+impl<T> Deref for AutoShrink<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.shard
+    }
+}
+
+impl<T> Iterator for AutoShrink<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let res = self.shard.next();
+        self.maybe_shrink();
+        res
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.shard.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for AutoShrink<T> {
+    fn next_back(&mut self) -> Option<T> {
+        let res = self.shard.next_back();
+        self.maybe_shrink();
+        res
+    }
+}
+
+impl<T> ExactSizeIterator for AutoShrink<T> {}
+
+impl<T> FusedIterator for AutoShrink<T> {}
+
+/// A draining iterator that yields the elements of a shard from back to front.
+///
+/// This is created by [`VecShard::drain_rev`](crate::VecShard::drain_rev).
+#[derive(Debug)]
+pub struct DrainRev<T> {
+    pub(crate) shard: VecShard<T>,
+}
+
+impl<T> Iterator for DrainRev<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.shard.next_back()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.shard.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for DrainRev<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.shard.next()
+    }
+}
+
+impl<T> ExactSizeIterator for DrainRev<T> {}
+
+impl<T> FusedIterator for DrainRev<T> {}
+
+/// An iterator over the elements that were removed from a shard by a splice.
+///
+/// This is created by [`VecShard::splice`](crate::VecShard::splice).
+/// The replacement has already happened by then, so dropping this iterator
+/// only drops the removed elements that are left.
+#[derive(Debug)]
+pub struct Splice<T> {
+    pub(crate) removed: VecShard<T>,
+}
+
+impl<T> Iterator for Splice<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.removed.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.removed.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Splice<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.removed.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Splice<T> {}
+
+impl<T> FusedIterator for Splice<T> {}
+
+/// An iterator that removes the elements of a shard that match a filter.
+///
+/// This is created by [`VecShard::extract_if`](crate::VecShard::extract_if).
+/// Dropping it puts the elements that were kept back together.
+#[derive(Debug)]
+pub struct ExtractIf<'a, T, F> {
+    pub(crate) shard: &'a mut VecShard<T>,
+    // the next element to look at
+    pub(crate) idx: usize,
+    // how many elements have been removed so far
+    pub(crate) del: usize,
+    pub(crate) old_len: usize,
+    pub(crate) filter: F,
+}
+
+impl<T, F: FnMut(&mut T) -> bool> Iterator for ExtractIf<'_, T, F> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let data = self.shard.data.as_ptr();
+        while self.idx < self.old_len {
+            let i = self.idx;
+            let extract = (self.filter)(unsafe { &mut *data.add(i) });
+            self.idx += 1;
+            if extract {
+                self.del += 1;
+                return Some(unsafe { data.add(i).read() });
+            } else if self.del > 0 {
+                unsafe { ptr::copy_nonoverlapping(data.add(i), data.add(i - self.del), 1) };
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.old_len - self.idx))
+    }
+}
+
+impl<T, F: FnMut(&mut T) -> bool> FusedIterator for ExtractIf<'_, T, F> {}
+
+impl<T, F> Drop for ExtractIf<'_, T, F> {
+    fn drop(&mut self) {
+        // Close the gap in front of the elements we haven't looked at, which are all kept
+        let data = self.shard.data.as_ptr();
+        if self.del > 0 {
+            unsafe {
+                ptr::copy(
+                    data.add(self.idx),
+                    data.add(self.idx - self.del),
+                    self.old_len - self.idx,
+                )
+            };
+        }
+        self.shard.len = self.old_len - self.del;
+        self.shard.cap += self.del;
+    }
+}
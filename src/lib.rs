@@ -75,6 +75,24 @@ assert_eq!(Some('e'), shard.next());
 assert_eq!(*shard, ['e', 't']);
 ```
 
+# Panics
+
+Only a handful of methods can panic, and all of them only on invalid arguments:
+
+- [`split_inplace_at`](crate::ShardExt::split_inplace_at), [`split_inplace_at_tagged`](crate::VecShard::split_inplace_at_tagged),
+  [`split_disjoint`](crate::VecShard::split_disjoint), [`split_with_capacity`](crate::VecShard::split_with_capacity)
+  and [`split_where`](crate::VecShard::split_where), if the index is out of bounds
+- [`splice`](crate::VecShard::splice), if the range is out of bounds
+- [`split_inplace_into`](crate::ShardExt::split_inplace_into), [`into_shard_chunks`](crate::VecShard::into_shard_chunks),
+  [`chunks_mut_owned`](crate::VecShard::chunks_mut_owned), [`drop_in_batches`](crate::VecShard::drop_in_batches)
+  and [`hash_chunks`](crate::VecShard::hash_chunks),
+  if asked for zero-sized pieces
+- indexing and [`at`](crate::VecShard::at), just like with slices
+- [`auto_shrink`](crate::VecShard::auto_shrink), if the threshold isn't in `0.0..1.0`
+
+These are all `#[track_caller]`, so the panic message points at your code rather than into this crate.
+Apart from that, anything that allocates can fail just like the corresponding `Vec` method would.
+
 # Optional Features
 
 This crate has zero dependencies by default, but if you want to serialize and deserialize `VecShard`,
@@ -86,27 +104,108 @@ optional = true
 version = "0.2.1"
 ```
 
+For byte shards, the [`serde_layout`](crate::serde_layout) module serializes them as one blob
+instead of element by element, and can frame them with a length prefix for custom protocols.
+
+Enabling the `rayon` feature gets you [`par_sort`](crate::VecShard::par_sort),
+a parallel mergesort that splits and merges shards in place, and [`par_fold`](crate::VecShard::par_fold),
+a parallel reduction over the elements of a shard.
+
+The `debug-tracking` feature is a debugging aid for code that builds shards with `unsafe`.
+With it, every allocation remembers which of its elements have been dropped by a shard,
+and dropping the same element twice panics instead of silently corrupting memory.
+This costs a lock and a scan on every drop, so don't leave it on in production.
+Without the feature, none of this bookkeeping is compiled in.
+
+The shards of an allocation keep track of each other with an atomic reference count, like an `Arc`.
+On targets without threads, such as plain `wasm32-unknown-unknown`, those atomics are pure overhead,
+and the `wasm-single-thread` feature swaps them for a non-atomic count, like an `Rc`.
+The API stays the same otherwise, but shards are no longer `Send` or `Sync`,
+so they can't be moved to or shared with other threads anymore.
+This makes the feature non-additive: turning it on takes something away from every crate in the build
+that depends on this one. It also rules out the parallel methods from the `rayon` feature,
+so enabling both is a compile error rather than quietly dropping them.
+
 [`VecShard`]: crate::VecShard
 */
 
 use std::{
     borrow::{Borrow, BorrowMut},
     cmp::{Eq, PartialEq},
+    collections::{HashMap, HashSet, TryReserveError, VecDeque},
+    convert::{Infallible, TryFrom},
     fmt,
     hash::{Hash, Hasher},
     iter::FusedIterator,
-    mem,
-    ops::{Deref, DerefMut, Index, IndexMut},
-    ptr,
-    slice::{self, SliceIndex},
+    marker::PhantomData,
+    mem::{self, MaybeUninit},
+    ops::{Bound, Deref, DerefMut, Index, IndexMut, Range, RangeBounds},
+    ptr::{self, NonNull},
+    slice::{self, GetDisjointMutError, SliceIndex},
     sync::Arc,
 };
 
 pub mod error;
-use crate::error::{CantMerge, WouldAlloc, WouldMove};
-
+pub mod iter;
+use crate::error::{CantMerge, ConversionError, ConversionFailure, WouldAlloc, WouldMove};
+use crate::iter::{AutoShrink, DrainRev, DropBatches, ExtractIf, ShardChunks, Splice};
+
+mod cow;
+mod shared;
+mod text;
+pub use crate::cow::ShardCow;
+pub use crate::shared::SharedShard;
+
+#[cfg(feature = "rayon")]
+mod par;
+
+// Features are unified across the whole build, so quietly leaving out the parallel API
+// would break whichever crate asked for it
+#[cfg(all(feature = "rayon", feature = "wasm-single-thread"))]
+compile_error!(
+    "the `rayon` and `wasm-single-thread` features can't be enabled together, \
+     since parallel methods need shards that can be sent to other threads"
+);
+
+// All shards of one allocation share its dropper through a reference count.
+// If they can't ever leave their thread, that count doesn't need to be atomic.
+#[cfg(feature = "wasm-single-thread")]
+use std::rc::Rc as Refcount;
+#[cfg(not(feature = "wasm-single-thread"))]
+use std::sync::Arc as Refcount;
 #[cfg(feature = "serde")]
 mod serde_impl;
+#[cfg(feature = "serde")]
+pub use crate::serde_impl::{AsTuple, TupleSeed};
+#[cfg(feature = "serde")]
+pub mod serde_layout;
+
+/// Make a [`VecShard`](crate::VecShard) from a list of elements, just like `vec!`.
+///
+/// Both forms of `vec!` work: `shard![a, b, c]` for a list of elements
+/// and `shard![x; n]` for `n` clones of `x`, the latter going through
+/// [`from_elem`](crate::VecShard::from_elem).
+///
+/// ```
+/// # use vecshard::shard;
+/// let shard = shard![1, 2, 3];
+/// assert_eq!(*shard, [1, 2, 3]);
+///
+/// let zeroes = shard![0u8; 4];
+/// assert_eq!(*zeroes, [0, 0, 0, 0]);
+/// ```
+#[macro_export]
+macro_rules! shard {
+    () => {
+        $crate::VecShard::new()
+    };
+    ($elem:expr; $n:expr) => {
+        $crate::VecShard::from_elem($elem, $n)
+    };
+    ($($x:expr),+ $(,)?) => {
+        $crate::VecShard::from(::std::vec![$($x),+])
+    };
+}
 
 /// An extension trait for things that can be split into shards
 ///
@@ -129,13 +228,166 @@ pub trait ShardExt {
     /// This is an O(1) operation, as it keeps the underlying storage.
     /// In exchange, this means that the memory will not be reclaimed until
     /// all existing shards using it are dropped.
+    ///
+    /// Panics if `at` is greater than the length.
     fn split_inplace_at(self, at: usize) -> (Self::Shard, Self::Shard);
+
+    /// Split this array into `n` shards of (almost) equal length.
+    /// If the length isn't divisible by `n`, the first shards get one element more than the rest.
+    /// Just like [`split_inplace_at`](crate::ShardExt::split_inplace_at),
+    /// each split takes O(1) time and all shards share the same storage.
+    ///
+    /// Panics if `n` is 0.
+    ///
+    /// ```
+    /// # use vecshard::ShardExt;
+    /// let shards = vec![1, 2, 3, 4, 5, 6, 7].split_inplace_into(3);
+    ///
+    /// assert_eq!(*shards[0], [1, 2, 3]);
+    /// assert_eq!(*shards[1], [4, 5]);
+    /// assert_eq!(*shards[2], [6, 7]);
+    /// ```
+    fn split_inplace_into(self, n: usize) -> Vec<Self::Shard>;
+}
+
+/// Which of the two shards from a split starts at the front of the backing allocation.
+///
+/// See [`split_inplace_at_tagged`](crate::VecShard::split_inplace_at_tagged).
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Side {
+    Left,
+    Right,
+    Neither,
+}
+
+/// Which end of the backing allocation a merged shard should end up at.
+///
+/// See [`merge_toward`](crate::VecShard::merge_toward).
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Direction {
+    Low,
+    High,
+}
+
+/// What merging two shards would take.
+///
+/// See [`merge_cost`](crate::VecShard::merge_cost).
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum MergeCost {
+    /// The shards can be merged in O(1), nothing has to move.
+    InPlace,
+    /// Elements have to be moved around within the allocation, but nothing is allocated.
+    Move,
+    /// The elements have to be copied into a new allocation.
+    Allocate,
+}
+
+/// When [`merge_with_policy`](crate::VecShard::merge_with_policy) should give up on moving elements
+/// around within their allocation and copy them into a new one instead.
+///
+/// The default never does, which is what [`merge`](crate::VecShard::merge) does too.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct MergePolicy {
+    /// How many bytes worth of elements a merge may move within the allocation.
+    /// Merges that would have to move more than that allocate instead.
+    pub max_move_bytes: usize,
+}
+
+impl Default for MergePolicy {
+    fn default() -> Self {
+        MergePolicy {
+            max_move_bytes: usize::MAX,
+        }
+    }
+}
+
+/// What it took to turn a shard into a Vec.
+///
+/// See [`into_vec_stats`](crate::VecShard::into_vec_stats).
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct ConversionStats {
+    /// Whether the Vec lives in the shard's old allocation.
+    pub reused_allocation: bool,
+    /// How many bytes worth of elements had to be moved, either to the front
+    /// of the old allocation or into a new one.
+    pub bytes_copied: usize,
+    /// How many shards were using the old allocation right before the conversion, including this one.
+    pub shard_count: usize,
+}
+
+/// Whether a `T` takes up exactly as much room as a `U`, so one can be reinterpreted as the other.
+const fn same_layout<T, U>() -> bool {
+    mem::size_of::<T>() == mem::size_of::<U>() && mem::align_of::<T>() == mem::align_of::<U>()
 }
 
 /// The raw guts of a Vec, used to free its allocation when all the shards are gone.
+// repr(C) so that `VecShard::cast` can rely on the layout not depending on T
+#[repr(C)]
 struct VecDropper<T> {
-    ptr: *mut T,
+    ptr: NonNull<T>,
     capacity: usize,
+    // How many elements the Vec had when it was turned into a shard
+    origin_len: usize,
+    // The element ranges that shards have dropped so far
+    #[cfg(feature = "debug-tracking")]
+    dropped: std::sync::Mutex<Vec<Range<usize>>>,
+}
+
+impl<T> VecDropper<T> {
+    fn new(ptr: NonNull<T>, capacity: usize, origin_len: usize) -> Self {
+        VecDropper {
+            ptr,
+            capacity,
+            origin_len,
+            #[cfg(feature = "debug-tracking")]
+            dropped: Default::default(),
+        }
+    }
+
+    /// Record that the elements in `range` are being dropped, and panic if any of them already were.
+    #[cfg(feature = "debug-tracking")]
+    fn track_drop(&self, range: Range<usize>) {
+        // ZSTs don't have distinct positions, so there's nothing to keep apart
+        if mem::size_of::<T>() == 0 || range.is_empty() {
+            return;
+        }
+        let mut dropped = self.dropped.lock().unwrap_or_else(|e| e.into_inner());
+        let clash = dropped
+            .iter()
+            .find(|r| r.start < range.end && range.start < r.end)
+            .cloned();
+        dropped.push(range.clone());
+        drop(dropped);
+
+        if let Some(clash) = clash {
+            panic!(
+                "elements {:?} of this allocation were already dropped by another shard, now {:?} are being dropped again",
+                clash, range
+            );
+        }
+    }
+
+    #[cfg(not(feature = "debug-tracking"))]
+    fn track_drop(&self, _range: Range<usize>) {}
+
+    /// Forget about all dropped ranges, because their slots are being filled with live elements again.
+    ///
+    /// Only call this while there are no other shards that could still be dropping elements.
+    #[cfg(feature = "debug-tracking")]
+    fn forget_drops(&self) {
+        self.dropped.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    #[cfg(not(feature = "debug-tracking"))]
+    fn forget_drops(&self) {}
+
+    /// Give up the allocation without freeing it, because something else takes care of it now.
+    fn into_raw(self) -> (NonNull<T>, usize) {
+        let this = mem::ManuallyDrop::new(self);
+        #[cfg(feature = "debug-tracking")]
+        drop(unsafe { ptr::read(&this.dropped) });
+        (this.ptr, this.capacity)
+    }
 }
 
 impl<T> Drop for VecDropper<T> {
@@ -143,57 +395,1884 @@ impl<T> Drop for VecDropper<T> {
         unsafe {
             // Set len to 0 because we only want to free the memory.
             // Dropping the elements themselves is taken care of by the shards.
-            mem::drop(Vec::from_raw_parts(self.ptr, 0, self.capacity));
+            mem::drop(Vec::from_raw_parts(self.ptr.as_ptr(), 0, self.capacity));
         }
     }
 }
 
+/// Get the dropper that all empty shards without an allocation share, so that making one doesn't allocate.
+///
+/// It looks just like the dropper of an empty Vec: its pointer is dangling and its capacity is 0.
+/// The dangling pointer only depends on the alignment of `T`, so there's one dropper per alignment,
+/// which is only allocated the first time it's needed. Each of them keeps one reference for itself,
+/// so it's never the only one left and never freed.
+fn empty_dropper<T>() -> Refcount<VecDropper<T>> {
+    let slot = mem::align_of::<T>().trailing_zeros() as usize;
+    // It's only neither `Send` nor `Sync` because of the pointer, which doesn't point at anything
+    #[allow(clippy::arc_with_non_send_sync)]
+    let init = || Refcount::new(VecDropper::new(NonNull::<T>::dangling().cast::<()>(), 0, 0));
+
+    #[cfg(not(feature = "wasm-single-thread"))]
+    let dropper = {
+        struct Slot(std::sync::OnceLock<Refcount<VecDropper<()>>>);
+        // There's nothing behind the pointer, so there's nothing that threads could race on either
+        unsafe impl Sync for Slot {}
+        static DROPPERS: [Slot; usize::BITS as usize] =
+            [const { Slot(std::sync::OnceLock::new()) }; usize::BITS as usize];
+
+        DROPPERS[slot].0.get_or_init(init).clone()
+    };
+
+    // A non-atomic refcount can't be shared between threads, so every thread gets its own
+    #[cfg(feature = "wasm-single-thread")]
+    let dropper = {
+        thread_local! {
+            static DROPPERS: [std::cell::OnceCell<Refcount<VecDropper<()>>>; usize::BITS as usize] =
+                const { [const { std::cell::OnceCell::new() }; usize::BITS as usize] };
+        }
+        // While the thread is shutting down, an empty shard can't hurt to have a dropper of its own
+        DROPPERS
+            .try_with(|droppers| droppers[slot].get_or_init(init).clone())
+            .unwrap_or_else(|_| init())
+    };
+
+    // The layout doesn't depend on T, see `VecShard::cast`
+    unsafe { Refcount::from_raw(Refcount::into_raw(dropper) as *const VecDropper<T>) }
+}
+
 /// A shard of a [`Vec<T>`](std::vec::Vec), can be used mostly like a Vec.
 ///
 /// The major difference is that, when dropped, [`VecShard<T>`](crate::VecShard)
 /// will not immediately free its allocated memory.
 /// Instead, it will only drop all its items.
 /// The memory itself will be freed once all VecShards from the Vec are gone.
+///
+/// # Borrowing
+///
+/// Borrows of a shard's elements work just like they do for a Vec: they're tied to the shard itself,
+/// not to the allocation, so they can't outlive the shard, even if its siblings keep the memory alive.
+/// The same goes for raw pointers from [`as_ptr`](slice::as_ptr) and friends,
+/// except that the compiler can't check those for you: once the shard is dropped, merged or
+/// drained, pointers into it must not be used anymore, even though the memory may still be allocated.
+///
+/// ```compile_fail
+/// # use vecshard::ShardExt;
+/// let (left, right) = vec![1, 2, 3].split_inplace_at(1);
+/// let slice = &*left;
+/// drop(left);
+/// // right still keeps the allocation alive, but left's elements are gone
+/// assert_eq!(slice, [1]);
+/// ```
+///
+/// ```compile_fail
+/// # use vecshard::{ShardExt, VecShard};
+/// let (left, right) = vec![1, 2, 3].split_inplace_at(1);
+/// let first = &left[0];
+/// let merged = VecShard::merge(left, right);
+/// assert_eq!(*first, 1);
+/// ```
+///
+/// ```compile_fail
+/// # use vecshard::VecShard;
+/// let mut shard = VecShard::from(vec![1, 2, 3]);
+/// let first = shard.peek_front().unwrap();
+/// // draining the shard moves the element out from under the reference
+/// let owned = shard.next();
+/// assert_eq!(*first, 1);
+/// ```
+///
+/// # Layout
+///
+/// The layout of a shard is unspecified and may change between versions, so don't rely on it,
+/// e.g. when handing shards to foreign code. Pass a pointer and length from
+/// [`as_ptr`](slice::as_ptr) and [`len`](slice::len) instead, or hand over an opaque pointer to the whole shard.
+/// It's deliberately not `repr(C)`, which lets `Option<VecShard<T>>` stay the same size as a shard.
 pub struct VecShard<T> {
-    dropper: Arc<VecDropper<T>>,
+    dropper: Refcount<VecDropper<T>>,
 
-    data: *mut T,
+    data: NonNull<T>,
     len: usize,
+    // How many free slots directly behind the last element belong to this shard.
+    // No other shard owns any of them, so the shard can grow into them without asking anyone.
+    cap: usize,
+
+    // We own the elements, so make sure dropck knows about that
+    _marker: PhantomData<T>,
 }
 
-// These are the same as for Vec<T>
-// Probably sound, since the only thing we share is the Arc
+// These are the same as for Vec<T>.
+// Shards from the same Vec never share any elements, the only thing they share is the refcount,
+// which is fine to use from several threads at once (unless it's the non-atomic one).
+
+/// A shard can be sent to another thread if its elements can, even if its siblings stay behind.
+///
+/// ```compile_fail
+/// # use vecshard::VecShard;
+/// fn assert_send<T: Send>(_: T) {}
+/// assert_send(VecShard::from(vec![std::rc::Rc::new(())]));
+/// ```
+#[cfg(not(feature = "wasm-single-thread"))]
 unsafe impl<T: Send> Send for VecShard<T> {}
+
+/// A shard can be shared between threads if its elements can.
+///
+/// ```compile_fail
+/// # use vecshard::VecShard;
+/// fn assert_sync<T: Sync>(_: T) {}
+/// assert_sync(VecShard::from(vec![std::cell::Cell::new(0)]));
+/// ```
+#[cfg(not(feature = "wasm-single-thread"))]
 unsafe impl<T: Sync> Sync for VecShard<T> {}
 
 impl<T> VecShard<T> {
-    fn into_raw_parts(self) -> (Arc<VecDropper<T>>, *mut T, usize) {
-        let dropper = unsafe { ptr::read(&self.dropper as *const Arc<VecDropper<T>>) };
+    /// Make a new, empty shard.
+    ///
+    /// Just like `Vec::new`, this doesn't allocate anything.
+    /// All empty shards share their bookkeeping, which is only allocated once.
+    pub fn new() -> Self {
+        VecShard {
+            dropper: empty_dropper(),
+            data: NonNull::dangling(),
+            len: 0,
+            // Not even for ZSTs, so that a shard that grows gets a dropper of its own
+            cap: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Make a new, empty shard with room for exactly `total` elements, like `Vec::with_capacity`.
+    ///
+    /// This is meant for producers that know up front how much they're going to write:
+    /// fill the buffer through [`spare_capacity_mut`](crate::VecShard::spare_capacity_mut)
+    /// and [`set_len`](crate::VecShard::set_len) (or just [`push`](crate::VecShard::push)),
+    /// then hand out pieces of it with [`split_inplace_into`](crate::ShardExt::split_inplace_into),
+    /// all without ever reallocating or copying.
+    ///
+    /// ```
+    /// # use vecshard::{ShardExt, VecShard};
+    /// let mut buf = VecShard::prepare_buffer(6);
+    /// for (i, slot) in buf.spare_capacity_mut().iter_mut().enumerate() {
+    ///     slot.write(i * i);
+    /// }
+    /// unsafe { buf.set_len(6) };
+    ///
+    /// let pieces = buf.split_inplace_into(3);
+    /// assert_eq!(*pieces[1], [4, 9]);
+    /// ```
+    pub fn prepare_buffer(total: usize) -> Self {
+        VecShard::from(Vec::with_capacity(total))
+    }
+
+    /// Make a shard out of an uninitialized buffer whose first `len` slots have been filled in.
+    ///
+    /// This takes over the buffer's allocation, so it's meant for buffer pools that keep
+    /// their storage around as `Box<[MaybeUninit<T>]>`. The slots after the first `len` become the shard's
+    /// spare capacity. Use [`into_uninit`](crate::VecShard::into_uninit) to get a buffer back for the pool.
+    ///
+    /// # Safety
+    ///
+    /// - `len` must be less than or equal to `boxed.len()`
+    /// - the first `len` slots of `boxed` must be initialized
+    ///
+    /// The shard owns those elements from then on and drops them as usual.
+    /// Whatever is in the other slots is never dropped.
+    ///
+    /// ```
+    /// # use std::mem::MaybeUninit;
+    /// # use vecshard::VecShard;
+    /// let mut buf: Box<[MaybeUninit<u32>]> = Box::new_uninit_slice(4);
+    /// buf[0].write(1);
+    /// buf[1].write(2);
+    ///
+    /// let shard = unsafe { VecShard::from_uninit(buf, 2) };
+    /// assert_eq!(*shard, [1, 2]);
+    /// assert_eq!(shard.capacity(), 4);
+    /// ```
+    pub unsafe fn from_uninit(boxed: Box<[MaybeUninit<T>]>, len: usize) -> Self {
+        debug_assert!(len <= boxed.len());
+        let capacity = boxed.len();
+        let ptr = Box::into_raw(boxed) as *mut T;
+        VecShard::from(Vec::from_raw_parts(ptr, len, capacity))
+    }
+
+    /// Make a new shard over some slots of `owner`'s backing allocation.
+    ///
+    /// `range` is counted from the start of the allocation, not from the start of `owner`.
+    /// The new shard shares the allocation like a sibling from a split would, so the allocation
+    /// stays alive until both are gone. This lets several parts of a program carve their own shards
+    /// out of one buffer, e.g. one that was filled in through
+    /// [`spare_capacity_mut`](crate::VecShard::spare_capacity_mut).
+    ///
+    /// # Safety
+    ///
+    /// - `range` must lie within the allocation, that is `range.end` must be at most the capacity
+    ///   of the Vec the allocation came from
+    /// - the elements in `range` must be initialized
+    /// - no other shard may own any of the slots in `range`, neither as elements nor as spare capacity.
+    ///   If they came from `owner`'s spare capacity, `owner` must not touch its spare capacity afterwards.
+    ///
+    /// The new shard owns the elements in `range` and drops them as usual. It has no spare capacity.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let mut owner = VecShard::prepare_buffer(4);
+    /// for (i, slot) in owner.spare_capacity_mut().iter_mut().enumerate() {
+    ///     slot.write(i);
+    /// }
+    ///
+    /// let front = unsafe { VecShard::from_shared_parts(&owner, 0..2) };
+    /// let back = unsafe { VecShard::from_shared_parts(&owner, 2..4) };
+    /// // owner is empty, and its spare capacity belongs to the others now
+    /// drop(owner);
+    ///
+    /// assert_eq!(*front, [0, 1]);
+    /// assert_eq!(*back, [2, 3]);
+    /// ```
+    pub unsafe fn from_shared_parts(owner: &Self, range: Range<usize>) -> Self {
+        debug_assert!(range.start <= range.end && range.end <= owner.dropper.capacity);
+        VecShard {
+            dropper: owner.dropper.clone(),
+            data: owner.dropper.ptr.add(range.start),
+            len: range.end - range.start,
+            cap: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    fn into_raw_parts(self) -> (Refcount<VecDropper<T>>, NonNull<T>, usize) {
+        let dropper = unsafe { ptr::read(&self.dropper as *const Refcount<VecDropper<T>>) };
         let data = self.data;
         let len = self.len;
         mem::forget(self);
         (dropper, data, len)
     }
 
-    /// Try to merge the given shards without moving them around.
+    /// The index of this shard's first element within the backing allocation.
+    fn offset(&self) -> usize {
+        if mem::size_of::<T>() == 0 {
+            // ZSTs don't take up any space, so every shard starts at the front
+            0
+        } else {
+            unsafe { self.data.offset_from(self.dropper.ptr) as usize }
+        }
+    }
+
+    /// How many shards are left in this shard's allocation, including itself.
     ///
-    /// This can only succeed if `left` and `right` were split off from the same Vec
-    /// and are directly adjacent to each other.
-    /// Furthermore, `right` needs to be at a higher address than left so the elements stay in the right order.
+    /// Empty shards without an allocation share their dropper (see `empty_dropper`),
+    /// but they don't share anything else, so each of them counts as the only one.
+    fn shard_count(&self) -> usize {
+        if self.dropper.capacity == 0 {
+            1
+        } else {
+            Refcount::strong_count(&self.dropper)
+        }
+    }
+
+    /// Check whether this shard still holds exactly the elements of the Vec it was made from.
     ///
-    /// Returns the merged shard on success and an `Err` otherwise.
+    /// That's the case as long as it hasn't been split (or has been merged back together in place),
+    /// or drained. Such a shard can always be turned back into a Vec in O(1),
+    /// getting the original allocation and capacity back without moving anything.
+    ///
+    /// ```
+    /// # use vecshard::{ShardExt, VecShard};
+    /// let shard = VecShard::from(vec![1, 2, 3]);
+    /// assert!(shard.is_whole());
+    ///
+    /// let (left, right) = shard.split_inplace_at(1);
+    /// assert!(!left.is_whole());
+    ///
+    /// let shard = VecShard::merge_inplace(left, right).unwrap();
+    /// assert!(shard.is_whole());
+    /// ```
+    pub fn is_whole(&self) -> bool {
+        // See `into_vec_reporting` for why the refcount can be trusted
+        self.shard_count() == 1
+            && self.offset() == 0
+            && self.len == self.dropper.origin_len
+    }
+
+    /// The number of elements this shard can hold without reallocating.
+    ///
+    /// A shard's spare capacity is the free room directly behind its elements that no other shard owns.
+    /// When splitting a Vec, that's only the shard at the back, since the spare room of any other shard
+    /// would be in the way of its siblings. Some methods, like [`split_with_capacity`](crate::VecShard::split_with_capacity),
+    /// leave free room behind other shards too.
+    ///
+    /// ```
+    /// # use vecshard::{ShardExt, VecShard};
+    /// let mut vec = Vec::with_capacity(10);
+    /// vec.extend_from_slice(&[1, 2, 3, 4]);
+    ///
+    /// let (left, right) = vec.split_inplace_at(2);
+    /// assert_eq!(left.capacity(), 2);
+    /// assert_eq!(right.capacity(), 8);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.len + self.cap
+    }
+
+    /// Make sure that this shard can hold at least `additional` more elements without reallocating.
+    ///
+    /// If the shard doesn't have enough spare capacity of its own, its elements are moved
+    /// into a bigger allocation, using the same growth strategy as Vec. If it's the only shard
+    /// left in its allocation, that allocation is grown instead.
+    pub fn reserve(&mut self, additional: usize) {
+        if self.cap >= additional {
+            return;
+        }
+        // Grow geometrically like Vec, so that pushing one element at a time stays amortized O(1)
+        let capacity = self
+            .len
+            .saturating_add(additional)
+            .max(self.len.saturating_mul(2));
+        let Ok(()) = self.grow_to::<Infallible>(capacity, |vec, n| {
+            vec.reserve_exact(n);
+            Ok(())
+        });
+    }
+
+    /// Make sure that this shard can hold at least `additional` more elements without reallocating,
+    /// without reserving any more room than that.
+    ///
+    /// This is like [`reserve`](crate::VecShard::reserve), except that if the shard has to grow,
+    /// its capacity ends up at exactly `len + additional`, just like with `Vec::reserve_exact`.
+    /// A shard that shares its allocation with others can't grow in place, so its elements are
+    /// moved into a new allocation of exactly that size.
+    ///
+    /// ```
+    /// # use vecshard::ShardExt;
+    /// let (mut left, right) = vec![1, 2, 3, 4].split_inplace_at(2);
+    ///
+    /// left.reserve_exact(3);
+    /// assert_eq!(left.capacity(), 5);
+    /// # drop(right);
+    /// ```
+    pub fn reserve_exact(&mut self, additional: usize) {
+        if self.cap >= additional {
+            return;
+        }
+        let capacity = self.len.saturating_add(additional);
+        let Ok(()) = self.grow_to::<Infallible>(capacity, |vec, n| {
+            vec.reserve_exact(n);
+            Ok(())
+        });
+    }
+
+    /// Like [`reserve_exact`](crate::VecShard::reserve_exact), but returns an error instead of aborting
+    /// if the allocation fails.
+    ///
+    /// On error, the shard still holds all of its elements. If it shares its allocation with others,
+    /// it stays right where it was; if it's alone, its elements may have been moved to the front.
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        if self.cap >= additional {
+            return Ok(());
+        }
+        let capacity = self.len.saturating_add(additional);
+        self.grow_to(capacity, |vec, n| vec.try_reserve_exact(n))
+    }
+
+    /// Move this shard into an allocation that can hold at least `capacity` elements.
+    ///
+    /// `reserve_exact(vec, n)` must make room for `n` more elements in `vec`, like `Vec::reserve_exact`.
+    /// If this is the only shard left, its own allocation is grown, otherwise a new one is allocated
+    /// and the elements are copied over exactly once. If `reserve_exact` fails, a shared shard is left untouched.
+    fn grow_to<E>(
+        &mut self,
+        capacity: usize,
+        reserve_exact: impl FnOnce(&mut Vec<T>, usize) -> Result<(), E>,
+    ) -> Result<(), E> {
+        // See `into_vec_reporting` for why the refcount can be trusted
+        if Refcount::strong_count(&self.dropper) == 1 {
+            let mut vec = Vec::from(mem::take(self));
+            let additional = capacity.saturating_sub(vec.len());
+            let res = reserve_exact(&mut vec, additional);
+            *self = VecShard::from(vec);
+            return res;
+        }
+
+        let mut vec = Vec::new();
+        reserve_exact(&mut vec, capacity)?;
+        let (_dropper, data, len) = mem::take(self).into_raw_parts();
+        unsafe {
+            ptr::copy_nonoverlapping(data.as_ptr(), vec.as_mut_ptr(), len);
+            vec.set_len(len);
+        }
+        *self = VecShard::from(vec);
+        Ok(())
+    }
+
+    /// Move the elements of this shard into an allocation that fits them exactly.
+    ///
+    /// If this is the only shard left in its allocation, the allocation is shrunk,
+    /// otherwise the elements are copied over to a new one.
+    /// Either way, this shard stops keeping the old allocation alive.
+    pub fn shrink_to_fit(&mut self) {
+        let shard = mem::take(self);
+        let mut vec = Vec::from(shard);
+        vec.shrink_to_fit();
+        *self = VecShard::from(vec);
+    }
+
+    /// Take over the whole backing allocation, if this is the only shard left in it.
+    ///
+    /// Once all of its siblings are gone, the space they used to take up is free, but a shard
+    /// doesn't notice that on its own. This moves the elements to the front of the allocation
+    /// and makes everything behind them this shard's spare capacity, so you can
+    /// [`push`](crate::VecShard::push) into it without reallocating.
+    /// If there are other shards left, this does nothing.
+    ///
+    /// ```
+    /// # use vecshard::ShardExt;
+    /// let (left, mut right) = vec![1, 2, 3, 4].split_inplace_at(2);
+    /// drop(left);
+    /// assert_eq!(right.capacity(), 2);
+    ///
+    /// right.reclaim_capacity();
+    /// assert_eq!(*right, [3, 4]);
+    /// assert_eq!(right.capacity(), 4);
+    /// ```
+    pub fn reclaim_capacity(&mut self) {
+        // See `into_vec_reporting` for why the refcount can be trusted
+        if Refcount::strong_count(&self.dropper) != 1 {
+            return;
+        }
+
+        let base = self.dropper.ptr;
+        if self.data != base {
+            unsafe { ptr::copy(self.data.as_ptr(), base.as_ptr(), self.len) };
+            self.data = base;
+        }
+        self.cap = self.dropper.capacity - self.len;
+        self.dropper.forget_drops();
+    }
+
+    /// Append an element to the back of this shard.
+    ///
+    /// This takes amortized O(1) time, just like `Vec::push`.
+    /// As long as the shard has some spare capacity, the element is written right behind the others.
+    /// Otherwise, the shard is reallocated like with [`reserve`](crate::VecShard::reserve).
+    pub fn push(&mut self, value: T) {
+        if self.cap == 0 {
+            self.reserve(1);
+        }
+        unsafe { self.data.add(self.len).write(value) };
+        self.len += 1;
+        self.cap -= 1;
+    }
+
+    /// Move all elements of `other` onto the end of this shard.
+    ///
+    /// This works like [`Vec::append`], except that it consumes `other`.
+    /// The elements are moved over into this shard's spare capacity, which is grown like with
+    /// [`reserve`](crate::VecShard::reserve) if needed. If this shard is empty and too small, it
+    /// takes over `other`'s allocation instead, so nothing is copied at all.
+    ///
+    /// ```
+    /// # use vecshard::ShardExt;
+    /// let (mut left, _right) = vec![1, 2, 3].split_inplace_at(1);
+    ///
+    /// left.append_vec(vec![4, 5]);
+    /// assert_eq!(*left, [1, 4, 5]);
+    /// ```
+    pub fn append_vec(&mut self, mut other: Vec<T>) {
+        // A Vec never shares an allocation with a shard, so there's no way to merge in place here
+        if self.len == 0 && self.cap < other.len() {
+            *self = VecShard::from(other);
+            return;
+        }
+
+        let count = other.len();
+        self.reserve(count);
+        unsafe {
+            ptr::copy_nonoverlapping(other.as_ptr(), self.data.add(self.len).as_ptr(), count);
+            // The elements belong to this shard now, `other` only frees its allocation
+            other.set_len(0);
+        }
+        self.len += count;
+        self.cap -= count;
+    }
+
+    /// Get the spare capacity of this shard as a slice of uninitialized elements.
+    ///
+    /// Like [`Vec::spare_capacity_mut`], this lets you write elements behind the end of the shard
+    /// and then mark them as initialized with [`set_len`](crate::VecShard::set_len),
+    /// without checking the capacity for every single element.
+    ///
+    /// The spare capacity belongs to this shard alone, so no other shard can see what you write there.
+    /// After a plain split, only the shard at the back of its allocation has any though, for all others this slice is empty.
+    /// Use [`reserve`](crate::VecShard::reserve) first to make sure there's enough room.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let mut shard = VecShard::from(vec![0u32]);
+    /// shard.reserve(3);
+    ///
+    /// for (i, slot) in shard.spare_capacity_mut()[..3].iter_mut().enumerate() {
+    ///     slot.write(i as u32 + 1);
+    /// }
+    /// unsafe { shard.set_len(4) };
+    ///
+    /// assert_eq!(*shard, [0, 1, 2, 3]);
+    /// ```
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        unsafe {
+            slice::from_raw_parts_mut(
+                self.data.add(self.len).as_ptr() as *mut MaybeUninit<T>,
+                self.cap,
+            )
+        }
+    }
+
+    /// Set the length of this shard, without dropping or initializing anything.
+    ///
+    /// This is the shard version of [`Vec::set_len`]. Slots that are cut off by a shorter length
+    /// go back to being spare capacity, so their elements are leaked, not dropped.
+    ///
+    /// # Safety
+    ///
+    /// - `new_len` must be less than or equal to [`capacity()`](crate::VecShard::capacity)
+    /// - the elements at `old_len..new_len` must be initialized, e.g. through
+    ///   [`spare_capacity_mut`](crate::VecShard::spare_capacity_mut)
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        debug_assert!(new_len <= self.capacity());
+        self.cap = self.len + self.cap - new_len;
+        self.len = new_len;
+    }
+
+    /// Replace the elements in `range` with the ones from `replace_with`, and get back the ones that were removed.
+    ///
+    /// This works like [`Vec::splice`], except that the replacement happens right away
+    /// instead of when the returned iterator is dropped.
+    ///
+    /// As long as `replace_with` yields at most as many elements as were removed,
+    /// everything happens inside this shard's own slots, even if it still has siblings.
+    /// Any slots left over at the end become spare capacity.
+    /// If there are more, the shard has to grow like with [`reserve`](crate::VecShard::reserve).
+    ///
+    /// If `replace_with` panics, the elements behind `range` are leaked rather than dropped.
+    ///
+    /// Panics if the range is out of bounds or if its start is greater than its end.
+    ///
+    /// ```
+    /// # use vecshard::ShardExt;
+    /// let (mut left, _right) = vec![1, 2, 3, 4, 5].split_inplace_at(3);
+    ///
+    /// let removed: Vec<_> = left.splice(1..2, vec![20, 21]).collect();
+    /// assert_eq!(removed, [2]);
+    /// assert_eq!(*left, [1, 20, 21, 3]);
+    /// ```
+    #[track_caller]
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<T>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = T>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i.checked_add(1).expect("range start overflows"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&i) => i.checked_add(1).expect("range end overflows"),
+            Bound::Excluded(&i) => i,
+            Bound::Unbounded => self.len,
+        };
+        assert!(start <= end, "range start {} is greater than its end {}", start, end);
+        assert!(end <= self.len, "range end {} is out of bounds for length {}", end, self.len);
+
+        let gap = end - start;
+        let tail = self.len - end;
+
+        let mut removed = Vec::with_capacity(gap);
+        let mut iter = replace_with.into_iter();
+        let mut filled = 0;
+        unsafe {
+            ptr::copy_nonoverlapping(self.data.add(start).as_ptr(), removed.as_mut_ptr(), gap);
+            removed.set_len(gap);
+
+            // Forget about the tail while the gap isn't filled, so a panic can only leak it
+            self.len = start;
+            while filled < gap {
+                match iter.next() {
+                    Some(x) => self.data.add(start + filled).write(x),
+                    None => break,
+                }
+                filled += 1;
+            }
+
+            // Close whatever is left of the gap, the freed slots at the end become spare capacity
+            if filled < gap {
+                let data = self.data.as_ptr();
+                ptr::copy(data.add(end), data.add(start + filled), tail);
+                self.cap += gap - filled;
+            }
+            self.len = start + filled + tail;
+        }
+
+        // If there's more, there's no way around growing the shard
+        if let Some(next) = iter.next() {
+            let mut vec = Vec::from(mem::take(self));
+            vec.splice(start + gap..start + gap, std::iter::once(next).chain(iter));
+            *self = VecShard::from(vec);
+        }
+
+        Splice {
+            removed: VecShard::from(removed),
+        }
+    }
+
+    /// Remove the elements for which `filter` returns `true`, and iterate over them.
+    ///
+    /// This works like [`Vec::extract_if`] over the whole shard. The elements that are kept
+    /// are moved together within this shard's own slots as the iterator goes along,
+    /// and the slots that become free at the end turn into spare capacity.
+    ///
+    /// If the iterator is dropped early, the remaining elements are all kept, as if `filter` had returned `false` for them.
+    /// The same goes for the element `filter` was looking at if it panics.
+    /// If the iterator is leaked (e.g. with `mem::forget`), so are the elements of this shard.
+    ///
+    /// ```
+    /// # use vecshard::ShardExt;
+    /// let (mut left, _right) = vec![1, 2, 3, 4, 5, 6].split_inplace_at(5);
+    ///
+    /// let evens: Vec<_> = left.extract_if(|x| *x % 2 == 0).collect();
+    /// assert_eq!(evens, [2, 4]);
+    /// assert_eq!(*left, [1, 3, 5]);
+    /// ```
+    pub fn extract_if<F: FnMut(&mut T) -> bool>(&mut self, filter: F) -> ExtractIf<'_, T, F> {
+        let old_len = self.len;
+        // Forget about the elements until the iterator is done with them, so leaking it can only leak them
+        self.len = 0;
+        ExtractIf {
+            shard: self,
+            idx: 0,
+            del: 0,
+            old_len,
+            filter,
+        }
+    }
+
+    /// Split this shard into two at the given index, without checking that the index is in bounds.
+    ///
+    /// This is exactly like [`split_inplace_at`](crate::ShardExt::split_inplace_at),
+    /// minus the bounds check. Only reach for it if you've already validated the index
+    /// and the check actually shows up in your profile.
+    ///
+    /// # Safety
+    ///
+    /// `at` must be less than or equal to `self.len()`.
+    pub unsafe fn split_inplace_at_unchecked(mut self, at: usize) -> (Self, Self) {
+        let right = VecShard {
+            dropper: self.dropper.clone(),
+            data: self.data.add(at),
+            len: self.len - at,
+            cap: self.cap,
+            _marker: PhantomData,
+        };
+
+        // for the left shard, just cut ourselves down to size
+        // the right shard is in the way now, so we don't get to keep any spare capacity either
+        self.len = at;
+        self.cap = 0;
+
+        (self, right)
+    }
+
+    /// Split this shard into two halves.
+    ///
+    /// If the length is odd, the left half gets the extra element.
+    /// This is the same O(1) split as `split_inplace_at`, it just saves you the arithmetic
+    /// and keeps the rounding the same everywhere in recursive algorithms.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let (left, right) = VecShard::from(vec![1, 2, 3, 4, 5]).split_in_half();
+    ///
+    /// assert_eq!(*left, [1, 2, 3]);
+    /// assert_eq!(*right, [4, 5]);
+    /// ```
+    pub fn split_in_half(self) -> (Self, Self) {
+        let at = self.len - self.len / 2;
+        self.split_inplace_at(at)
+    }
+
+    /// Split this shard into two at the given index, so that both halves have room to grow.
+    ///
+    /// A plain [`split_inplace_at`](crate::ShardExt::split_inplace_at) leaves all the spare capacity
+    /// to the right half, since it's the one at the back. The left half is directly followed by the right one,
+    /// so it can't grow at all without reallocating.
+    /// If this shard is the only one in its allocation and has spare capacity, this splits the capacity instead:
+    /// the right half's elements are moved back by half of it, leaving the left half a gap to grow into.
+    /// The left half gets the smaller share if the spare capacity is odd.
+    ///
+    /// Otherwise, this is exactly `split_inplace_at`, so the right half gets whatever spare capacity there is.
+    ///
+    /// Moving the right half takes O(n) time. The halves aren't adjacent afterwards,
+    /// so merging them back together moves elements too.
+    ///
+    /// Panics if `at > self.len()`.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let mut vec = Vec::with_capacity(10);
+    /// vec.extend_from_slice(&[1, 2, 3, 4]);
+    ///
+    /// let (mut left, mut right) = VecShard::from(vec).split_with_capacity(2);
+    /// assert_eq!(left.capacity(), 5);
+    /// assert_eq!(right.capacity(), 5);
+    ///
+    /// for i in 5..8 {
+    ///     left.push(i);
+    ///     right.push(i * 10);
+    /// }
+    /// assert_eq!(*left, [1, 2, 5, 6, 7]);
+    /// assert_eq!(*right, [3, 4, 50, 60, 70]);
+    /// ```
+    #[track_caller]
+    pub fn split_with_capacity(self, at: usize) -> (Self, Self) {
+        let gap = self.cap / 2;
+        // See `into_vec_reporting` for why the refcount can be trusted
+        if gap == 0 || mem::size_of::<T>() == 0 || Refcount::strong_count(&self.dropper) != 1 {
+            return self.split_inplace_at(at);
+        }
+
+        let (mut left, mut right) = self.split_inplace_at(at);
+        unsafe {
+            let dest = right.data.add(gap);
+            ptr::copy(right.data.as_ptr(), dest.as_ptr(), right.len);
+            right.data = dest;
+        }
+        left.cap = gap;
+        right.cap -= gap;
+        // The elements moved to places that may have been dropped from before
+        right.dropper.forget_drops();
+        (left, right)
+    }
+
+    /// Split this shard in front of the first element that matches `pred`.
+    ///
+    /// Finding the element takes O(n) time, the split itself is O(1) like `split_inplace_at`.
+    /// If no element matches, the right shard is empty.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let shard = VecShard::from(vec![2, 4, 6, 7, 8]);
+    /// let (even, rest) = shard.split_at_predicate(|x| x % 2 == 1);
+    ///
+    /// assert_eq!(*even, [2, 4, 6]);
+    /// assert_eq!(*rest, [7, 8]);
+    /// ```
+    pub fn split_at_predicate<P: FnMut(&T) -> bool>(self, pred: P) -> (Self, Self) {
+        let at = self.iter().position(pred).unwrap_or(self.len);
+        self.split_inplace_at(at)
+    }
+
+    /// Split this shard at an index computed by `f` from its contents.
+    ///
+    /// `f` gets to look at all of the elements and returns where to split,
+    /// e.g. by reading a length prefix or scanning for a delimiter.
+    /// The split itself is O(1) like `split_inplace_at`.
+    ///
+    /// Panics if `f` returns an index greater than the length of the shard.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// // one frame with a 3 byte payload, followed by the start of the next frame
+    /// let buf = VecShard::from(vec![3u8, b'a', b'b', b'c', 5, b'd']);
+    ///
+    /// let (frame, rest) = buf.split_where(|bytes| 1 + bytes[0] as usize);
+    ///
+    /// assert_eq!(*frame, [3, b'a', b'b', b'c']);
+    /// assert_eq!(*rest, [5, b'd']);
+    /// ```
+    #[track_caller]
+    pub fn split_where<F: FnOnce(&[T]) -> usize>(self, f: F) -> (Self, Self) {
+        let at = f(&self);
+        self.split_inplace_at(at)
+    }
+
+    /// Split this shard into at most `n` shards at the elements that match `pred`,
+    /// like [`slice::splitn`](https://doc.rust-lang.org/std/primitive.slice.html#method.splitn).
+    ///
+    /// The separators themselves are dropped. The last shard contains the rest of the elements,
+    /// including any remaining separators. All pieces keep sharing the original allocation.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let shard = VecShard::from(Vec::from("GET / HTTP/1.1"));
+    /// let parts = shard.splitn_owned(2, |&b| b == b' ');
+    ///
+    /// assert_eq!(parts[0].as_str(), Ok("GET"));
+    /// assert_eq!(parts[1].as_str(), Ok("/ HTTP/1.1"));
+    /// ```
+    pub fn splitn_owned<P: FnMut(&T) -> bool>(self, n: usize, mut pred: P) -> Vec<Self> {
+        let mut pieces = Vec::new();
+        if n == 0 {
+            return pieces;
+        }
+
+        let mut rest = self;
+        while pieces.len() + 1 < n {
+            match rest.iter().position(&mut pred) {
+                Some(at) => {
+                    let (piece, tail) = rest.cut_at(at);
+                    pieces.push(piece);
+                    rest = tail;
+                }
+                None => break,
+            }
+        }
+        pieces.push(rest);
+        pieces
+    }
+
+    /// Split this shard at the elements that match `pred`, starting from the back,
+    /// like [`slice::rsplit`](https://doc.rust-lang.org/std/primitive.slice.html#method.rsplit).
+    ///
+    /// The separators themselves are dropped. The pieces come out last one first,
+    /// so a trailing separator gives an empty shard up front. All pieces keep sharing the original allocation.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let shard = VecShard::from(Vec::from("usr/local/bin"));
+    /// let parts = shard.rsplit_owned(|&b| b == b'/');
+    ///
+    /// assert_eq!(parts[0].as_str(), Ok("bin"));
+    /// assert_eq!(parts[2].as_str(), Ok("usr"));
+    /// ```
+    pub fn rsplit_owned<P: FnMut(&T) -> bool>(self, mut pred: P) -> Vec<Self> {
+        let mut pieces = Vec::new();
+        let mut rest = self;
+        while let Some(at) = rest.iter().rposition(&mut pred) {
+            let (head, piece) = rest.cut_at(at);
+            pieces.push(piece);
+            rest = head;
+        }
+        pieces.push(rest);
+        pieces
+    }
+
+    /// Split this shard into runs of consecutive elements that belong together,
+    /// like [`slice::chunk_by`](https://doc.rust-lang.org/std/primitive.slice.html#method.chunk_by).
+    ///
+    /// A new run starts wherever `same_group(prev, next)` returns `false` for two neighbouring elements.
+    /// Finding the runs takes O(n) time, the splits themselves are O(1) each and all runs keep sharing
+    /// the original allocation. An empty shard has no runs at all.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let runs = VecShard::from(vec![1, 1, 2, 3, 3, 3]).group_runs(|a, b| a == b);
+    ///
+    /// assert_eq!(runs.len(), 3);
+    /// assert_eq!(*runs[0], [1, 1]);
+    /// assert_eq!(*runs[1], [2]);
+    /// assert_eq!(*runs[2], [3, 3, 3]);
+    /// ```
+    pub fn group_runs<F: FnMut(&T, &T) -> bool>(self, mut same_group: F) -> Vec<Self> {
+        let mut groups = Vec::new();
+        let mut rest = self;
+        while rest.len > 0 {
+            match rest.windows(2).position(|pair| !same_group(&pair[0], &pair[1])) {
+                Some(i) => {
+                    let (group, tail) = rest.split_inplace_at(i + 1);
+                    groups.push(group);
+                    rest = tail;
+                }
+                None => {
+                    // The last run gets to keep any spare capacity
+                    groups.push(rest);
+                    break;
+                }
+            }
+        }
+        groups
+    }
+
+    /// Split this shard around the element at `at` and drop that element.
+    fn cut_at(self, at: usize) -> (Self, Self) {
+        let (left, rest) = self.split_inplace_at(at);
+        // The separator gets its own shard, so it's dropped exactly once, along with that shard
+        let (separator, right) = rest.split_inplace_at(1);
+        mem::drop(separator);
+        (left, right)
+    }
+
+    /// Split this shard into two at the given index, getting two shards that never overlap.
+    ///
+    /// This is exactly [`split_inplace_at`](crate::ShardExt::split_inplace_at) under a name that spells out
+    /// the guarantee algorithms with two mutable halves rely on: the left shard owns the elements before `mid`,
+    /// the right shard owns the rest, and no element is owned by both. Each can be mutated, drained or dropped
+    /// without ever affecting the other one.
+    ///
+    /// Panics if `mid > self.len()`, just like `split_inplace_at`.
+    #[track_caller]
+    pub fn split_disjoint(self, mid: usize) -> (Self, Self) {
+        self.split_inplace_at(mid)
+    }
+
+    /// Split this shard into owning shards over the given ranges, if they don't overlap.
+    ///
+    /// The ranges can be in any order and don't have to cover the whole shard.
+    /// You get one shard per range, in the order of `ranges`, each owning exactly the elements in its range.
+    /// The elements that aren't in any range are dropped. Empty ranges get empty shards.
+    /// Like with a split, everything stays in the same allocation.
+    ///
+    /// If any two ranges overlap, or any range is out of bounds or reversed, no two owning shards could exist
+    /// over them and you get this shard back untouched as the `Err`.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let shard = VecShard::from(vec![0, 1, 2, 3, 4, 5]);
+    ///
+    /// let shard = shard.try_subshards(&[1..3, 2..4]).unwrap_err();
+    ///
+    /// let pieces = shard.try_subshards(&[4..6, 0..2]).unwrap();
+    /// assert_eq!(*pieces[0], [4, 5]);
+    /// assert_eq!(*pieces[1], [0, 1]);
+    /// ```
+    pub fn try_subshards(self, ranges: &[Range<usize>]) -> Result<Vec<Self>, Self> {
+        let mut order: Vec<usize> = (0..ranges.len()).collect();
+        order.sort_by_key(|&i| (ranges[i].start, ranges[i].end));
+
+        // the end of the last non-empty range so far
+        let mut end = 0;
+        for &i in &order {
+            let range = &ranges[i];
+            if range.start > range.end || range.end > self.len {
+                return Err(self);
+            }
+            if !range.is_empty() {
+                if range.start < end {
+                    return Err(self);
+                }
+                end = range.end;
+            }
+        }
+
+        let mut pieces: Vec<Option<Self>> = ranges.iter().map(|_| None).collect();
+        let mut rest = self;
+        let mut pos = 0;
+        for &i in &order {
+            let range = &ranges[i];
+            if !range.is_empty() {
+                rest.skip_shard(range.start - pos);
+                pos = range.start;
+            }
+            pieces[i] = Some(rest.take_shard(range.len()));
+            pos += range.len();
+        }
+        Ok(pieces.into_iter().map(Option::unwrap).collect())
+    }
+
+    /// Split this shard into two at the given index and tell which of them
+    /// starts at the front of the backing allocation.
+    ///
+    /// A shard that starts at the front of its allocation can be turned into a Vec
+    /// without moving its elements once all the other shards are gone, so this lets you
+    /// route your data so that the eventual conversion is as cheap as possible.
+    /// The right shard only starts at the front if the left one is empty,
+    /// so an empty left shard counts as [`Side::Right`].
+    ///
+    /// Panics if `at > self.len()`, just like `split_inplace_at`.
+    ///
+    /// ```
+    /// # use vecshard::{ShardExt, Side, VecShard};
+    /// let shard = VecShard::from(vec![1, 2, 3, 4]);
+    ///
+    /// let (left, right, side) = shard.split_inplace_at_tagged(1);
+    /// assert_eq!(side, Side::Left);
+    ///
+    /// let (_, _, side) = right.split_inplace_at_tagged(1);
+    /// assert_eq!(side, Side::Neither);
+    /// ```
+    #[track_caller]
+    pub fn split_inplace_at_tagged(self, at: usize) -> (Self, Self, Side) {
+        let at_front = self.offset() == 0;
+        let (left, right) = self.split_inplace_at(at);
+        let side = match (at_front, at) {
+            (false, _) => Side::Neither,
+            (true, 0) => Side::Right,
+            (true, _) => Side::Left,
+        };
+        (left, right, side)
+    }
+
+    /// Collect a sorted iterator into a shard, skipping consecutive duplicates.
+    ///
+    /// The input should be sorted (or at least have all equal elements next to each other),
+    /// otherwise only the duplicates that happen to be next to each other are removed.
+    /// The duplicates are dropped on the fly, and the resulting shard's allocation is
+    /// shrunk to fit exactly.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let shard = VecShard::from_sorted_dedup(vec![1, 1, 2, 3, 3, 3, 5]);
+    ///
+    /// assert_eq!(*shard, [1, 2, 3, 5]);
+    /// ```
+    pub fn from_sorted_dedup<I: IntoIterator<Item = T>>(iter: I) -> Self
+    where
+        T: PartialEq,
+    {
+        let iter = iter.into_iter();
+        let mut vec: Vec<T> = Vec::with_capacity(iter.size_hint().0);
+        for item in iter {
+            if vec.last() != Some(&item) {
+                vec.push(item);
+            }
+        }
+        vec.shrink_to_fit();
+        VecShard::from(vec)
+    }
+
+    /// Make a shard of `n` clones of `value`, just like `vec![value; n]`.
+    ///
+    /// The elements are written into a single allocation of exactly `n` elements.
+    /// This goes through `vec!` itself, so it gets the same fast paths as well,
+    /// e.g. zeroed memory for `vec![0; n]`.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let shard = VecShard::from_elem('z', 3);
+    ///
+    /// assert_eq!(*shard, ['z', 'z', 'z']);
+    /// assert_eq!(shard.capacity(), 3);
+    /// ```
+    pub fn from_elem(value: T, n: usize) -> Self
+    where
+        T: Clone,
+    {
+        VecShard::from(vec![value; n])
+    }
+
+    /// Split off the first `n` elements into their own shard, leaving the rest in `self`.
+    ///
+    /// This is the owning analog of [`Iterator::take`] and takes O(1) time, just like a split.
+    /// If `n` is greater than the length of the shard, the whole shard is taken.
+    pub fn take_shard(&mut self, n: usize) -> Self {
+        let n = n.min(self.len);
+        let front = VecShard {
+            dropper: self.dropper.clone(),
+            data: self.data,
+            len: n,
+            cap: 0,
+            _marker: PhantomData,
+        };
+        self.data = unsafe { self.data.add(n) };
+        self.len -= n;
+        front
+    }
+
+    /// Drop the first `n` elements of this shard.
+    ///
+    /// This is the owning analog of [`Iterator::skip`].
+    /// If `n` is greater than the length of the shard, all elements are dropped.
+    pub fn skip_shard(&mut self, n: usize) {
+        mem::drop(self.take_shard(n));
+    }
+
+    /// Turn this shard into a Vec, but return an error instead of aborting
+    /// if a new Vec is needed and it can't be allocated.
+    ///
+    /// If this is the only shard left in its allocation, it is re-used and this can't fail.
+    pub fn try_into_vec(self) -> Result<Vec<T>, TryReserveError> {
+        if Refcount::strong_count(&self.dropper) == 1 {
+            return Ok(Vec::from(self));
+        }
+
+        let mut vec = Vec::new();
+        vec.try_reserve_exact(self.len)?;
+        let (_dropper, data, len) = self.into_raw_parts();
+        unsafe {
+            ptr::copy_nonoverlapping(data.as_ptr(), vec.as_mut_ptr(), len);
+            vec.set_len(len);
+        }
+        Ok(vec)
+    }
+
+    /// Turn this shard into a [`vec::IntoIter`](std::vec::IntoIter).
+    ///
+    /// Unlike draining the shard itself, collecting this into a Vec again can re-use the allocation,
+    /// just like with `Vec::into_iter`. This first converts the shard into a Vec, so it only saves you an
+    /// allocation if this is the only shard left in its allocation.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let shard = VecShard::from(vec![1u32, 2, 3]);
+    ///
+    /// let doubled: Vec<u32> = shard.into_vec_iter().map(|x| x * 2).collect();
+    /// assert_eq!(doubled, [2, 4, 6]);
+    /// ```
+    pub fn into_vec_iter(self) -> std::vec::IntoIter<T> {
+        Vec::from(self).into_iter()
+    }
+
+    /// Turn this shard into a Vec and tell whether the backing allocation could be re-used.
+    ///
+    /// This does exactly the same as converting the shard `Into` a Vec, but also returns `true`
+    /// if the Vec lives in the shard's old allocation and `false` if a new one had to be allocated.
+    pub fn into_vec_reporting(self) -> (Vec<T>, bool) {
+        // Empty shards without an allocation share their dropper, but there's nothing to re-use anyway
+        if self.dropper.capacity == 0 {
+            return (Vec::new(), true);
+        }
+
+        // First, move everything out of the shard so we don't drop anything
+        let (dropper, data, len) = self.into_raw_parts();
+
+        // Optimization: if this shard is the only one left from the backing Vec, we re-use its allocation
+        // Every live shard holds exactly one reference to the dropper and gives it up when dropped,
+        // so the refcount tells us precisely whether our siblings are gone.
+        // The only way to keep a reference around is to leak a shard (e.g. through `ManuallyDrop`),
+        // and since such a shard can still be used afterwards, we must not touch its elements.
+        if let Ok(dropper) = Refcount::try_unwrap(dropper) {
+            // If our data is already at the start of the backing Vec, we don't need to move it
+            if data != dropper.ptr {
+                unsafe { ptr::copy(data.as_ptr(), dropper.ptr.as_ptr(), len) };
+            }
+            // Make sure we don't drop anything that the new Vec will need
+            let (ptr, capacity) = dropper.into_raw();
+            let v = unsafe { Vec::from_raw_parts(ptr.as_ptr(), len, capacity) };
+            (v, true)
+        } else {
+            // Otherwise, just allocate a new Vec
+            let mut v = Vec::with_capacity(len);
+            unsafe {
+                ptr::copy_nonoverlapping(data.as_ptr(), v.as_mut_ptr(), len);
+                v.set_len(len);
+            };
+            (v, false)
+        }
+    }
+
+    /// Turn this shard into a Vec and find out exactly how much work that was.
+    ///
+    /// This does the same as [`into_vec_reporting`](crate::VecShard::into_vec_reporting),
+    /// but gives you some more details, which comes in handy when tracking down unexpected copies.
+    ///
+    /// ```
+    /// # use vecshard::ShardExt;
+    /// let (left, right) = vec![1u32, 2, 3].split_inplace_at(1);
+    ///
+    /// let (vec, stats) = right.into_vec_stats();
+    /// assert_eq!(vec, [2, 3]);
+    /// assert!(!stats.reused_allocation);
+    /// assert_eq!(stats.bytes_copied, 8);
+    /// assert_eq!(stats.shard_count, 2);
+    /// # drop(left);
+    /// ```
+    pub fn into_vec_stats(self) -> (Vec<T>, ConversionStats) {
+        let shard_count = self.shard_count();
+        let at_front = self.offset() == 0;
+        let bytes = self.len * mem::size_of::<T>();
+
+        let (vec, reused_allocation) = self.into_vec_reporting();
+        let stats = ConversionStats {
+            reused_allocation,
+            bytes_copied: if reused_allocation && at_front { 0 } else { bytes },
+            shard_count,
+        };
+        (vec, stats)
+    }
+
+    /// Get the whole backing allocation as a Vec, leaving the elements where they are.
+    ///
+    /// A `Vec<T>` always starts at the front of its allocation, so turning a shard from further back
+    /// into one means moving the elements. If you'd rather keep them where they are, e.g. to prepend
+    /// more elements in front of them later, this gives you the allocation as a `Vec<MaybeUninit<T>>`
+    /// spanning all of it instead, along with the range that holds this shard's elements.
+    /// Everything outside that range is uninitialized. Nothing is moved or copied.
+    ///
+    /// The catch is that you're on your own from there: dropping the Vec doesn't drop the elements,
+    /// and you have to keep track of which slots are initialized yourself.
+    ///
+    /// This only works if this is the only shard left in its allocation. Otherwise, you get it back in the error,
+    /// with [`ConversionFailure::WouldAllocate`] as the reason.
+    ///
+    /// ```
+    /// # use vecshard::ShardExt;
+    /// let (left, right) = vec![1, 2, 3, 4].split_inplace_at(3);
+    /// drop(left);
+    ///
+    /// let (mut buf, range) = right.into_vec_keep_position().unwrap();
+    /// assert_eq!(range, 3..4);
+    ///
+    /// // put something in front of it
+    /// buf[2].write(30);
+    /// let values: Vec<i32> = (2..4).map(|i| unsafe { buf[i].assume_init() }).collect();
+    /// assert_eq!(values, [30, 4]);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn into_vec_keep_position(
+        self,
+    ) -> Result<(Vec<MaybeUninit<T>>, Range<usize>), ConversionError<T>> {
+        // An empty shard without an allocation can't be in anyone's way
+        if self.dropper.capacity == 0 {
+            return Ok((Vec::new(), 0..0));
+        }
+        // See `into_vec_reporting` for why the refcount can be trusted
+        if Refcount::strong_count(&self.dropper) != 1 {
+            return Err(ConversionError {
+                shard: self,
+                reason: ConversionFailure::WouldAllocate,
+            });
+        }
+
+        let start = self.offset();
+        let (dropper, _, len) = self.into_raw_parts();
+        let dropper = match Refcount::try_unwrap(dropper) {
+            Ok(dropper) => dropper,
+            Err(_) => unreachable!("the refcount was checked above"),
+        };
+        // The Vec owns the allocation now
+        let (ptr, capacity) = dropper.into_raw();
+        let vec =
+            unsafe { Vec::from_raw_parts(ptr.as_ptr() as *mut MaybeUninit<T>, capacity, capacity) };
+        Ok((vec, start..start + len))
+    }
+
+    /// Turn this shard into an uninitialized buffer, without dropping its elements.
+    ///
+    /// This is the way back from [`from_uninit`](crate::VecShard::from_uninit): the shard's elements end up
+    /// in the first `self.len()` slots of the buffer, and they are neither dropped nor leaked as far as
+    /// the buffer is concerned, since a `Box<[MaybeUninit<T>]>` never drops anything. If you care about them,
+    /// read them out with [`MaybeUninit::assume_init_read`] before reusing the slots.
+    ///
+    /// Like the conversion into a Vec, this re-uses the backing allocation (moving the elements to the front)
+    /// if this is the only shard left in it, so the buffer spans the whole allocation.
+    /// Otherwise, the elements are copied into a new buffer of exactly `self.len()` slots.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let shard = VecShard::from(vec![1u32, 2, 3]);
+    ///
+    /// let buf = shard.into_uninit();
+    /// assert_eq!(buf.len(), 3);
+    /// assert_eq!(unsafe { buf[2].assume_init() }, 3);
+    /// ```
+    pub fn into_uninit(self) -> Box<[MaybeUninit<T>]> {
+        let vec = mem::ManuallyDrop::new(Vec::from(self));
+        // A Vec of ZSTs claims to have room for `usize::MAX` of them, which is no use to a pool
+        let slots = if mem::size_of::<T>() == 0 {
+            vec.len()
+        } else {
+            vec.capacity()
+        };
+        let ptr = vec.as_ptr() as *mut MaybeUninit<T>;
+        // Every slot of a `MaybeUninit` buffer counts as initialized, so the length can cover the whole allocation,
+        // which also means that turning it into a box doesn't have to shrink it
+        unsafe { Vec::from_raw_parts(ptr, slots, slots) }.into_boxed_slice()
+    }
+
+    /// Turn this shard into a Vec with room for at least `min_capacity` elements.
+    ///
+    /// If this is the only shard left in its allocation, that allocation is re-used for the Vec,
+    /// and grown if it's too small. Otherwise, the elements are copied straight into
+    /// a new Vec of the requested capacity, so you don't pay for growing it right after the conversion.
+    ///
+    /// ```
+    /// # use vecshard::{ShardExt, VecShard};
+    /// let (left, _right) = vec![1, 2, 3, 4].split_inplace_at(2);
+    ///
+    /// let vec = left.into_vec_with_capacity(100);
+    /// assert_eq!(vec, [1, 2]);
+    /// assert!(vec.capacity() >= 100);
+    /// ```
+    pub fn into_vec_with_capacity(self, min_capacity: usize) -> Vec<T> {
+        // See `into_vec_reporting` for why the refcount can be trusted
+        if Refcount::strong_count(&self.dropper) == 1 {
+            let mut vec = Vec::from(self);
+            vec.reserve_exact(min_capacity.saturating_sub(vec.len()));
+            vec
+        } else {
+            let (_dropper, data, len) = self.into_raw_parts();
+            let mut vec = Vec::with_capacity(min_capacity.max(len));
+            unsafe {
+                ptr::copy_nonoverlapping(data.as_ptr(), vec.as_mut_ptr(), len);
+                vec.set_len(len);
+            }
+            vec
+        }
+    }
+
+    /// Map every element through `f`, re-using the allocation if possible.
+    ///
+    /// If `T` and `U` have the same size and alignment and this is the only shard left in its allocation,
+    /// each element is replaced by its image right where it is, so no new memory is needed for the elements.
+    /// Otherwise, this is the same as collecting `self.map(f)` into a new shard.
+    ///
+    /// If `f` panics, everything that has been mapped so far and everything that hasn't been gets dropped properly.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let shard = VecShard::from(vec![1u32, 2, 3]);
+    /// let ptr = shard.as_ptr() as *const i32;
+    ///
+    /// let negated = shard.map_inplace(|x| -(x as i32));
+    /// assert_eq!(*negated, [-1, -2, -3]);
+    /// assert_eq!(negated.as_ptr(), ptr);
+    /// ```
+    pub fn map_inplace<U, F: FnMut(T) -> U>(self, mut f: F) -> VecShard<U> {
+        // See `into_vec_reporting` for why the refcount can be trusted
+        if !same_layout::<T, U>() || Refcount::strong_count(&self.dropper) != 1 {
+            return self.map(f).collect::<Vec<U>>().into();
+        }
+
+        let mut vec = mem::ManuallyDrop::new(Vec::from(self));
+        let (ptr, len, capacity) = (vec.as_mut_ptr(), vec.len(), vec.capacity());
+
+        // If `f` panics, this cleans up the half-mapped Vec
+        struct Guard<T, U> {
+            ptr: *mut T,
+            len: usize,
+            capacity: usize,
+            done: usize,
+            _marker: PhantomData<U>,
+        }
+        impl<T, U> Drop for Guard<T, U> {
+            fn drop(&mut self) {
+                unsafe {
+                    // Everything in front of `done` is a U, the element at `done` has been
+                    // moved into `f` and everything behind it is still a T
+                    ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                        self.ptr as *mut U,
+                        self.done,
+                    ));
+                    let rest = self.done + 1;
+                    ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                        self.ptr.add(rest),
+                        self.len - rest,
+                    ));
+                    drop(Vec::from_raw_parts(self.ptr, 0, self.capacity));
+                }
+            }
+        }
+
+        let mut guard = Guard::<T, U> {
+            ptr,
+            len,
+            capacity,
+            done: 0,
+            _marker: PhantomData,
+        };
+        for i in 0..len {
+            unsafe {
+                let mapped = f(ptr.add(i).read());
+                (ptr.add(i) as *mut U).write(mapped);
+            }
+            guard.done = i + 1;
+        }
+        mem::forget(guard);
+
+        unsafe { Vec::from_raw_parts(ptr as *mut U, len, capacity) }.into()
+    }
+
+    /// Check that elements of type `T` could be reinterpreted as `U`, i.e. that both have the same size and alignment.
+    ///
+    /// The check happens at compile time, so a mismatch is a build error rather than a panic.
+    /// Like any check on generic parameters, it only runs once the types are known,
+    /// which means `cargo build` catches it but `cargo check` may not.
+    /// [`cast`](crate::VecShard::cast) does this check for you.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// VecShard::<u32>::assert_castable::<char>();
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use vecshard::VecShard;
+    /// VecShard::<u32>::assert_castable::<u64>();
+    /// ```
+    pub const fn assert_castable<U>() {
+        const {
+            assert!(
+                same_layout::<T, U>(),
+                "can't cast between types of different sizes or alignments"
+            )
+        }
+    }
+
+    /// Reinterpret the elements of this shard as another type of the same size and alignment.
+    ///
+    /// This is meant for wrapping and unwrapping `repr(transparent)` newtypes without copying anything.
+    /// The shard keeps sharing its allocation with its siblings, which keep their own element type.
+    ///
+    /// If `T` and `U` differ in size or alignment, this doesn't compile,
+    /// see [`assert_castable`](crate::VecShard::assert_castable).
+    ///
+    /// # Safety
+    ///
+    /// Every element must be a valid `U`, and so must anything that is later written into the shard's
+    /// spare capacity and read back as a `T` by its siblings. Dropping the shard drops its elements as `U`s.
+    /// For a `repr(transparent)` wrapper around `T`, these all hold, as long as the wrapper
+    /// doesn't have any extra invariants of its own.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// #[repr(transparent)]
+    /// #[derive(Debug, PartialEq)]
+    /// struct Id(u32);
+    ///
+    /// let ids: VecShard<Id> = unsafe { VecShard::from(vec![1u32, 2]).cast() };
+    /// assert_eq!(*ids, [Id(1), Id(2)]);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use vecshard::VecShard;
+    /// let wide: VecShard<u64> = unsafe { VecShard::from(vec![1u32, 2]).cast() };
+    /// ```
+    pub unsafe fn cast<U>(self) -> VecShard<U> {
+        Self::assert_castable::<U>();
+
+        let cap = self.cap;
+        let (dropper, data, len) = self.into_raw_parts();
+        // The dropper only frees the allocation, which has the same layout for U as for T
+        let dropper = Refcount::from_raw(Refcount::into_raw(dropper) as *const VecDropper<U>);
+        VecShard {
+            dropper,
+            data: data.cast(),
+            len,
+            cap,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Put this shard behind an `Arc` so it can be read from several places at once.
+    ///
+    /// See [`SharedShard`](crate::SharedShard) for a wrapper that derefs straight to a slice.
+    pub fn into_shared(self) -> Arc<VecShard<T>> {
+        Arc::new(self)
+    }
+
+    /// Drain this shard, releasing the big backing allocation once most of the elements are gone.
+    ///
+    /// A shard that has been drained mostly empty still keeps its whole allocation alive.
+    /// The returned iterator checks after every element whether fewer than `threshold` times
+    /// the backing allocation's capacity are left and, if so, moves the rest over with
+    /// [`shrink_to_fit`](crate::VecShard::shrink_to_fit).
+    ///
+    /// This bounds the memory a long-running consumer keeps around, at the cost of copying
+    /// the remaining elements once. Lower thresholds mean fewer elements to copy, but more memory wasted until then.
+    ///
+    /// `threshold` must be at least 0.0 (never shrink) and below 1.0, otherwise this panics.
+    /// At 1.0, every single element taken out would drop the shard below the threshold again,
+    /// so it would be copied over and over.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let mut drain = VecShard::from(vec![0u8; 1000]).auto_shrink(0.25);
+    ///
+    /// // once fewer than 250 elements are left, they get moved to a smaller allocation
+    /// drain.by_ref().take(800).for_each(drop);
+    /// assert_eq!(drain.into_inner().capacity(), 200);
+    /// ```
+    #[track_caller]
+    pub fn auto_shrink(self, threshold: f32) -> AutoShrink<T> {
+        assert!(
+            (0.0..1.0).contains(&threshold),
+            "auto_shrink threshold must be in 0.0..1.0, but is {}",
+            threshold
+        );
+        let mut drain = AutoShrink {
+            shard: self,
+            threshold,
+        };
+        drain.maybe_shrink();
+        drain
+    }
+
+    /// Drain this shard back to front.
+    ///
+    /// A shard is already a double-ended iterator, so this is what you'd get from `.rev()`,
+    /// just with a name of its own that spells out the order. If the iterator is dropped early,
+    /// the elements that are left at the front are dropped with it.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let drain = VecShard::from(vec![1, 2, 3]).drain_rev();
+    /// assert_eq!(drain.collect::<Vec<_>>(), [3, 2, 1]);
+    /// ```
+    pub fn drain_rev(self) -> DrainRev<T> {
+        DrainRev { shard: self }
+    }
+
+    /// Drop the elements of this shard in batches of `batch` elements each.
+    ///
+    /// Dropping a huge shard full of elements with expensive destructors can take a while,
+    /// all in one go. This returns an iterator that drops one batch per call to `next`,
+    /// returning the number of elements it dropped, so you get control back in between
+    /// (e.g. to yield to an async executor).
+    /// If the iterator itself is dropped early, the remaining elements are dropped all at once,
+    /// just like dropping the shard normally would.
+    ///
+    /// Panics if `batch` is 0.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let shard = VecShard::from(vec![String::from("schnitzel"); 10]);
+    ///
+    /// let mut batches = shard.drop_in_batches(4);
+    /// assert_eq!(batches.next(), Some(4));
+    /// // ..do something else..
+    /// assert_eq!(batches.collect::<Vec<_>>(), [4, 2]);
+    /// ```
+    #[track_caller]
+    pub fn drop_in_batches(self, batch: usize) -> DropBatches<T> {
+        assert!(batch > 0);
+        DropBatches { shard: self, batch }
+    }
+
+    /// Lazily split this shard into chunks of `size` elements each.
+    ///
+    /// Each call to `next` splits one chunk off the front of the shard in O(1),
+    /// so you never have to hold all of the chunks at once.
+    /// The last chunk may be shorter if `size` doesn't divide the length of the shard.
+    ///
+    /// Panics if `size` is 0.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let shard = VecShard::from(vec![1, 2, 3, 4, 5]);
+    /// let mut chunks = shard.into_shard_chunks(2);
+    ///
+    /// assert_eq!(*chunks.next().unwrap(), [1, 2]);
+    /// assert_eq!(*chunks.next().unwrap(), [3, 4]);
+    /// assert_eq!(*chunks.next().unwrap(), [5]);
+    /// assert!(chunks.next().is_none());
+    /// ```
+    #[track_caller]
+    pub fn into_shard_chunks(self, size: usize) -> ShardChunks<T> {
+        assert!(size > 0);
+        ShardChunks { shard: self, size }
+    }
+
+    /// Iterate over mutable chunks of `size` elements each, like [`slice::chunks_mut`].
+    ///
+    /// Unlike [`into_shard_chunks`](crate::VecShard::into_shard_chunks), this only borrows the shard,
+    /// so the chunks are plain slices that can't outlive it and the shard stays in one piece.
+    /// Since the chunks never overlap, they can be handed to different threads,
+    /// e.g. through rayon's `par_bridge`.
+    /// The last chunk may be shorter if `size` doesn't divide the length of the shard.
+    ///
+    /// Panics if `size` is 0.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let mut shard = VecShard::from(vec![1, 2, 3, 4, 5]);
+    /// for (i, chunk) in shard.chunks_mut_owned(2).enumerate() {
+    ///     chunk.iter_mut().for_each(|x| *x *= 10 * (i + 1));
+    /// }
+    ///
+    /// assert_eq!(*shard, [10, 20, 60, 80, 150]);
+    /// ```
+    #[track_caller]
+    pub fn chunks_mut_owned(&mut self, size: usize) -> impl Iterator<Item = &mut [T]> {
+        assert!(size > 0);
+        (**self).chunks_mut(size)
+    }
+
+    /// Consume the shard, returning its smallest element and dropping all the others.
+    ///
+    /// Like [`Iterator::min`], this returns the first one if there are several equal minima,
+    /// and `None` if the shard is empty.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let shard = VecShard::from(vec![3, 1, 4, 1, 5]);
+    ///
+    /// assert_eq!(shard.drain_min(), Some(1));
+    /// ```
+    pub fn drain_min(self) -> Option<T>
+    where
+        T: Ord,
+    {
+        let at = self.iter().enumerate().min_by_key(|&(_, x)| x)?.0;
+        self.drain_single(at)
+    }
+
+    /// Consume the shard, returning its largest element and dropping all the others.
+    ///
+    /// Like [`Iterator::max`], this returns the last one if there are several equal maxima,
+    /// and `None` if the shard is empty.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let shard = VecShard::from(vec![3, 1, 4, 1, 5]);
+    ///
+    /// assert_eq!(shard.drain_max(), Some(5));
+    /// ```
+    pub fn drain_max(self) -> Option<T>
+    where
+        T: Ord,
+    {
+        let at = self.iter().enumerate().max_by_key(|&(_, x)| x)?.0;
+        self.drain_single(at)
+    }
+
+    /// Move out the element at `at` and drop everything else.
+    fn drain_single(self, at: usize) -> Option<T> {
+        // the shards on either side drop their elements as usual
+        let (_front, mut back) = self.split_inplace_at(at);
+        back.next()
+    }
+
+    /// Check whether the two shards contain the same elements, in any order.
+    ///
+    /// Unlike `==`, this doesn't care about where in the shard the elements are,
+    /// only how often each of them appears. This comes in handy after a `merge_swapped`,
+    /// which may have put the elements in a different order.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let shard = VecShard::from(vec!['a', 'b', 'b']);
+    ///
+    /// assert!(shard.eq_multiset(&VecShard::from(vec!['b', 'a', 'b'])));
+    /// assert!(!shard.eq_multiset(&VecShard::from(vec!['a', 'a', 'b'])));
+    /// ```
+    pub fn eq_multiset(&self, other: &VecShard<T>) -> bool
+    where
+        T: Hash + Eq,
+    {
+        if self.len != other.len {
+            return false;
+        }
+
+        let mut counts = HashMap::with_capacity(self.len);
+        for e in &**self {
+            *counts.entry(e).or_insert(0isize) += 1;
+        }
+        for e in &**other {
+            match counts.get_mut(e) {
+                Some(0) | None => return false,
+                Some(count) => *count -= 1,
+            }
+        }
+        true
+    }
+
+    /// Look at the element that the next call to [`next`](std::iter::Iterator::next) would return,
+    /// without removing it.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let mut queue = VecShard::from(vec!["kaffee", "kuchen"]);
+    ///
+    /// assert_eq!(queue.peek_front(), Some(&"kaffee"));
+    /// assert_eq!(queue.next(), Some("kaffee"));
+    /// assert_eq!(queue.peek_front(), Some(&"kuchen"));
+    /// ```
+    pub fn peek_front(&self) -> Option<&T> {
+        (**self).first()
+    }
+
+    /// Iterate over the elements along with their indices in the original Vec.
+    ///
+    /// This is like `iter().enumerate()`, but the indices count from the front of the backing allocation,
+    /// so a shard that was split off at index 10 starts at 10, not at 0.
+    /// Elements that were pushed onto the shard just continue the count.
+    ///
+    /// Zero-sized elements don't take up any room, so there's no telling where in the Vec
+    /// a shard of them started out. Their indices always start at 0.
+    ///
+    /// ```
+    /// # use vecshard::ShardExt;
+    /// let (_, right) = vec!['a', 'b', 'c', 'd'].split_inplace_at(2);
+    ///
+    /// let indexed: Vec<_> = right.enumerate_global().collect();
+    /// assert_eq!(indexed, [(2, &'c'), (3, &'d')]);
+    /// ```
+    pub fn enumerate_global(&self) -> impl Iterator<Item = (usize, &T)> {
+        let offset = self.offset();
+        self.iter().enumerate().map(move |(i, x)| (offset + i, x))
+    }
+
+    /// Mutably iterate over the elements along with their indices in the original Vec.
+    ///
+    /// See [`enumerate_global`](crate::VecShard::enumerate_global) for how the indices are counted.
+    pub fn enumerate_global_mut(&mut self) -> impl Iterator<Item = (usize, &mut T)> {
+        let offset = self.offset();
+        self.iter_mut().enumerate().map(move |(i, x)| (offset + i, x))
+    }
+
+    /// Feed the elements of this shard to `state` in pieces of `chunk` elements each.
+    ///
+    /// This gives a streaming hasher (such as the standard library's `DefaultHasher`) the same input
+    /// as hashing the whole shard, length prefix included, so the result is the same too.
+    /// It just hands the data over in smaller bites, so you can e.g. check for cancellation
+    /// or report progress in between.
+    ///
+    /// Panics if `chunk` is 0.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::{Hash, Hasher};
+    ///
+    /// let shard = VecShard::from(vec![7u8; 1000]);
+    ///
+    /// let mut whole = DefaultHasher::new();
+    /// shard.hash(&mut whole);
+    /// let mut chunked = DefaultHasher::new();
+    /// shard.hash_chunks(&mut chunked, 64);
+    ///
+    /// assert_eq!(whole.finish(), chunked.finish());
+    /// ```
+    #[track_caller]
+    pub fn hash_chunks<H: Hasher>(&self, state: &mut H, chunk: usize)
+    where
+        T: Hash,
+    {
+        assert!(chunk > 0);
+        // this is what `Hash for [T]` starts with as well
+        state.write_usize(self.len);
+        for piece in self.chunks(chunk) {
+            T::hash_slice(piece, state);
+        }
+    }
+
+    /// Get a copy of the element at `index`.
+    ///
+    /// This is the same as `shard[index]` for `Copy` types, just with a bit less noise in numeric code.
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let shard = VecShard::from(vec![1.5, 2.5, 3.0]);
+    ///
+    /// let mut sum = 0.0;
+    /// for i in 0..shard.len() {
+    ///     sum += shard.at(i);
+    /// }
+    /// assert_eq!(sum, 7.0);
+    /// ```
+    #[track_caller]
+    pub fn at(&self, index: usize) -> T
+    where
+        T: Copy,
+    {
+        self[index]
+    }
+
+    /// Format the elements with `Display`, separated by `sep`.
+    ///
+    /// Unlike the `Debug` output, there are no brackets around the elements.
+    /// Any width, precision or other flags you format this with are applied to every element on its own.
+    ///
+    /// ```
+    /// # use vecshard::shard;
+    /// let shard = shard![1.5, 2.0, 3.25];
+    ///
+    /// assert_eq!(shard.display(", ").to_string(), "1.5, 2, 3.25");
+    /// assert_eq!(format!("{:.1}", shard.display(" | ")), "1.5 | 2.0 | 3.2");
+    /// ```
+    pub fn display<'a>(&'a self, sep: &'a str) -> impl fmt::Display + 'a
+    where
+        T: fmt::Display,
+    {
+        Joined { items: self, sep }
+    }
+
+    /// Look at the element that the next call to
+    /// [`next_back`](std::iter::DoubleEndedIterator::next_back) would return, without removing it.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let mut stack = VecShard::from(vec!["teller", "tasse"]);
+    ///
+    /// assert_eq!(stack.peek_back(), Some(&"tasse"));
+    /// assert_eq!(stack.next_back(), Some("tasse"));
+    /// assert_eq!(stack.peek_back(), Some(&"teller"));
+    /// ```
+    pub fn peek_back(&self) -> Option<&T> {
+        (**self).last()
+    }
+
+    /// Borrow the first element and the rest of the shard, without consuming anything.
+    ///
+    /// This is [`slice::split_first`], handy for recursing over the elements.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// fn sum(xs: &[u32]) -> u32 {
+    ///     match xs.split_first() {
+    ///         Some((x, rest)) => x + sum(rest),
+    ///         None => 0,
+    ///     }
+    /// }
+    ///
+    /// let shard = VecShard::from(vec![1, 2, 3]);
+    /// let (first, rest) = shard.split_first_ref().unwrap();
+    /// assert_eq!((*first, sum(rest)), (1, 5));
+    /// assert_eq!(shard.len(), 3);
+    /// ```
+    pub fn split_first_ref(&self) -> Option<(&T, &[T])> {
+        (**self).split_first()
+    }
+
+    /// Borrow the last element and the rest of the shard, without consuming anything.
+    ///
+    /// This is [`slice::split_last`].
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let shard = VecShard::from(vec!["a", "b", "c"]);
+    ///
+    /// assert_eq!(shard.split_last_ref(), Some((&"c", &["a", "b"][..])));
+    /// assert_eq!(VecShard::<u8>::new().split_last_ref(), None);
+    /// ```
+    pub fn split_last_ref(&self) -> Option<(&T, &[T])> {
+        (**self).split_last()
+    }
+
+    /// Get mutable references to several elements at once.
+    ///
+    /// This is just [`slice::get_disjoint_mut`] on the shard's elements, so it fails
+    /// if any of the indices is out of bounds or shows up more than once.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let mut shard = VecShard::from(vec![1, 2, 3]);
+    ///
+    /// let [a, c] = shard.get_disjoint_mut([0, 2]).unwrap();
+    /// std::mem::swap(a, c);
+    /// assert_eq!(*shard, [3, 2, 1]);
+    ///
+    /// assert!(shard.get_disjoint_mut([1, 1]).is_err());
+    /// ```
+    pub fn get_disjoint_mut<const N: usize>(
+        &mut self,
+        indices: [usize; N],
+    ) -> Result<[&mut T; N], GetDisjointMutError> {
+        (**self).get_disjoint_mut(indices)
+    }
+
+    /// Try to merge the given shards without moving them around.
+    ///
+    /// This can only succeed if `left` and `right` were split off from the same Vec
+    /// and are directly adjacent to each other.
+    /// Furthermore, `right` needs to be at a higher address than left so the elements stay in the right order.
+    ///
+    /// Returns the merged shard on success and an `Err` otherwise.
+    ///
+    /// If either shard is empty, this always succeeds and returns the other one.
     ///
     /// This function will always run in O(1) time.
     pub fn merge_inplace(left: Self, right: Self) -> Result<Self, CantMerge<T, WouldMove>> {
-        use WouldMove::*;
-        // Are the shards even from the same Vec?
-        if !Arc::ptr_eq(&left.dropper, &right.dropper) {
+        if let Err(reason) = Self::inplace_check(&left, &right) {
             Err(CantMerge {
-                reason: DifferentAllocations,
                 left,
                 right,
+                reason,
             })
-        } else if unsafe { left.data.add(left.len) } == right.data {
+        } else if left.len == 0 {
+            Ok(right)
+        } else if right.len == 0 {
+            Ok(left)
+        } else {
+            let cap = right.cap;
             let (ldropper, ldata, llen) = left.into_raw_parts();
             let (rdropper, _, rlen) = right.into_raw_parts();
             std::mem::drop(rdropper);
@@ -201,22 +2280,194 @@ impl<T> VecShard<T> {
                 dropper: ldropper,
                 data: ldata,
                 len: llen + rlen,
+                cap,
+                _marker: PhantomData,
             })
+        }
+    }
+
+    /// Like [`merge_inplace`](crate::VecShard::merge_inplace), but on error you get a plain tuple instead of a [`CantMerge`].
+    ///
+    /// That's handy in loops where failed merges are common, since the shards can be destructured right away.
+    ///
+    /// ```
+    /// # use vecshard::{error::WouldMove, ShardExt, VecShard};
+    /// let (left, right) = vec![1, 2, 3].split_inplace_at(1);
+    ///
+    /// let (right, left, reason) = VecShard::try_merge_inplace_light(right, left).unwrap_err();
+    /// assert_eq!(reason, WouldMove::WrongOrder);
+    /// assert_eq!(*VecShard::try_merge_inplace_light(left, right).unwrap(), [1, 2, 3]);
+    /// ```
+    pub fn try_merge_inplace_light(
+        left: Self,
+        right: Self,
+    ) -> Result<Self, (Self, Self, WouldMove)> {
+        Self::merge_inplace(left, right).map_err(|err| (err.left, err.right, err.reason))
+    }
+
+    /// Find out whether `merge_inplace` would work for these shards, and why not if it wouldn't.
+    fn inplace_check(left: &Self, right: &Self) -> Result<(), WouldMove> {
+        use WouldMove::*;
+        // Nothing to merge, no matter where the shards are
+        if left.len == 0 || right.len == 0 {
+            Ok(())
+        // Are the shards even from the same Vec?
+        } else if !Refcount::ptr_eq(&left.dropper, &right.dropper) {
+            Err(DifferentAllocations)
+        } else if unsafe { left.data.add(left.len) } == right.data {
+            Ok(())
         } else if unsafe { right.data.add(right.len) } == left.data {
-            Err(CantMerge {
-                left,
-                right,
-                reason: WrongOrder,
-            })
+            Err(WrongOrder)
         } else {
-            Err(CantMerge {
-                reason: NotAdjacent,
-                left,
-                right,
-            })
+            Err(NotAdjacent)
+        }
+    }
+
+    /// Find out what [`merge`](crate::VecShard::merge) would have to do to merge these shards, without merging them.
+    ///
+    /// This always agrees with what `merge` actually does:
+    /// - [`MergeCost::InPlace`] if [`merge_inplace`](crate::VecShard::merge_inplace) would work
+    /// - [`MergeCost::Move`] if [`merge_noalloc`](crate::VecShard::merge_noalloc) would have to move elements around
+    /// - [`MergeCost::Allocate`] if neither would work and the elements have to be copied into a new Vec
+    ///
+    /// This can help decide whether to merge right away or wait until some other shards are gone.
+    ///
+    /// ```
+    /// # use vecshard::{MergeCost, ShardExt, VecShard};
+    /// let (left, rest) = vec![1, 2, 3].split_inplace_at(1);
+    /// let (middle, right) = rest.split_inplace_at(1);
+    ///
+    /// assert_eq!(VecShard::merge_cost(&left, &middle), MergeCost::InPlace);
+    /// assert_eq!(VecShard::merge_cost(&middle, &left), MergeCost::Move);
+    /// // middle is still around and in the way
+    /// assert_eq!(VecShard::merge_cost(&left, &right), MergeCost::Allocate);
+    ///
+    /// drop(middle);
+    /// assert_eq!(VecShard::merge_cost(&left, &right), MergeCost::Move);
+    /// ```
+    pub fn merge_cost(left: &Self, right: &Self) -> MergeCost {
+        use WouldMove::*;
+        match Self::inplace_check(left, right) {
+            Ok(()) => MergeCost::InPlace,
+            Err(DifferentAllocations) => MergeCost::Allocate,
+            // Rotating doesn't touch anything outside the two shards
+            Err(WrongOrder) => MergeCost::Move,
+            // Anything in between has to be moved out of the way, so it can't belong to another shard
+            Err(NotAdjacent) if Refcount::strong_count(&left.dropper) == 2 => MergeCost::Move,
+            Err(NotAdjacent) => MergeCost::Allocate,
         }
     }
 
+    /// View two shards as one contiguous slice, without merging them.
+    ///
+    /// This works whenever `merge_inplace` would, that is if `right` directly follows `left`
+    /// in the same allocation. Otherwise, this returns `None`.
+    ///
+    /// ```
+    /// # use vecshard::{ShardExt, VecShard};
+    /// let (left, right) = vec![1, 2, 3].split_inplace_at(1);
+    ///
+    /// assert_eq!(VecShard::joined_slice(&left, &right), Some(&[1, 2, 3][..]));
+    /// assert_eq!(VecShard::joined_slice(&right, &left), None);
+    /// ```
+    pub fn joined_slice<'a>(left: &'a VecShard<T>, right: &'a VecShard<T>) -> Option<&'a [T]> {
+        if Refcount::ptr_eq(&left.dropper, &right.dropper)
+            && unsafe { left.data.add(left.len) } == right.data
+        {
+            Some(unsafe { slice::from_raw_parts(left.data.as_ptr(), left.len + right.len) })
+        } else {
+            None
+        }
+    }
+
+    /// Check whether two shards share any elements.
+    ///
+    /// Shards split off from each other never do, so this is only true if `a` and `b` are the same shard.
+    /// It's meant as a sanity check before handing out two shards to be drained independently.
+    /// Shards from different allocations never overlap, and neither do empty ones.
+    ///
+    /// ```
+    /// # use vecshard::{ShardExt, VecShard};
+    /// let (left, right) = vec![1, 2, 3].split_inplace_at(1);
+    ///
+    /// assert!(VecShard::overlaps(&left, &left));
+    /// assert!(!VecShard::overlaps(&left, &right));
+    /// ```
+    pub fn overlaps(a: &VecShard<T>, b: &VecShard<T>) -> bool {
+        if a.len == 0 || b.len == 0 || !Refcount::ptr_eq(&a.dropper, &b.dropper) {
+            return false;
+        }
+        if mem::size_of::<T>() == 0 {
+            // ZSTs all live at the same address, so only the shard itself can tell
+            return ptr::eq(a, b);
+        }
+        let (a_start, b_start) = (a.offset(), b.offset());
+        a_start < b_start + b.len && b_start < a_start + a.len
+    }
+
+    /// Find out which neighbouring shards in `shards` could be merged in place.
+    ///
+    /// This partitions the indices of `shards` into maximal runs, so that merging each run
+    /// from left to right with [`merge_inplace`](crate::VecShard::merge_inplace) would always succeed.
+    /// Shards from different allocations can be mixed freely, they just end up in separate runs.
+    /// Empty shards merge with anything, so they never start a new run.
+    ///
+    /// ```
+    /// # use vecshard::{ShardExt, VecShard};
+    /// let (a, rest) = vec![1, 2, 3].split_inplace_at(1);
+    /// let (b, c) = rest.split_inplace_at(1);
+    ///
+    /// let shards = [a, b, VecShard::from(vec![4]), c];
+    /// assert_eq!(VecShard::mergeable_groups(&shards), [0..2, 2..3, 3..4]);
+    /// ```
+    pub fn mergeable_groups(shards: &[VecShard<T>]) -> Vec<Range<usize>> {
+        let mut groups = Vec::new();
+        let mut start = 0;
+        // the last non-empty shard in the current run, if there is one yet
+        let mut last: Option<&VecShard<T>> = None;
+
+        for (i, shard) in shards.iter().enumerate() {
+            if shard.len == 0 {
+                continue;
+            }
+            if let Some(prev) = last {
+                if VecShard::joined_slice(prev, shard).is_none() {
+                    groups.push(start..i);
+                    start = i;
+                }
+            }
+            last = Some(shard);
+        }
+        if start < shards.len() {
+            groups.push(start..shards.len());
+        }
+        groups
+    }
+
+    /// Count how many bytes of backing memory `shards` keep alive between them.
+    ///
+    /// A shard keeps its whole backing allocation alive, not just its own elements,
+    /// so this can be a lot more than the shards' lengths add up to. Every allocation is only
+    /// counted once, no matter how many of the given shards share it.
+    /// Shards that aren't in `shards` may keep the same memory alive, of course.
+    ///
+    /// ```
+    /// # use vecshard::{ShardExt, VecShard};
+    /// let (left, right) = vec![0u32; 100].split_inplace_at(10);
+    /// drop(right);
+    ///
+    /// let shards = [left, VecShard::from(vec![1u32, 2])];
+    /// assert_eq!(VecShard::pinned_bytes(&shards), 408);
+    /// ```
+    pub fn pinned_bytes(shards: &[VecShard<T>]) -> usize {
+        let mut seen = HashSet::new();
+        shards
+            .iter()
+            .filter(|shard| seen.insert(Refcount::as_ptr(&shard.dropper)))
+            .map(|shard| shard.dropper.capacity * mem::size_of::<T>())
+            .sum()
+    }
+
     /// Try to merge the given shards without allocating a new `Vec`.
     ///
     /// This function will always succeed if the passed shards can be merged in-place
@@ -234,73 +2485,94 @@ impl<T> VecShard<T> {
             Err(err) => err,
         };
 
-        if cant_merge.reason == DifferentAllocations {
+        if Self::merge_cost(&cant_merge.left, &cant_merge.right) == MergeCost::Allocate {
             return Err(CantMerge {
+                reason: if cant_merge.reason == DifferentAllocations {
+                    WouldAlloc::DifferentAllocations
+                } else {
+                    WouldAlloc::OtherShardsLeft
+                },
                 left: cant_merge.left,
                 right: cant_merge.right,
-                reason: WouldAlloc::DifferentAllocations,
             });
         }
 
+        let lcap = cant_merge.left.cap;
         let (ldropper, ldata, llen) = cant_merge.left.into_raw_parts();
-        let (rdropper, rdata, rlen) = cant_merge.right.into_raw_parts();
+        let (_rdropper, rdata, rlen) = cant_merge.right.into_raw_parts();
 
         if cant_merge.reason == WrongOrder {
             // semi-fast path: we only need to rotate
-            unsafe { slice::from_raw_parts_mut(rdata, llen + rlen).rotate_left(rlen) };
+            unsafe { slice::from_raw_parts_mut(rdata.as_ptr(), llen + rlen).rotate_left(rlen) };
             Ok(VecShard {
                 dropper: ldropper,
                 data: rdata,
                 len: llen + rlen,
+                // left is the one at the back
+                cap: lcap,
+                _marker: PhantomData,
             })
-        } else if cant_merge.reason == NotAdjacent && Arc::strong_count(&ldropper) == 2 {
-            // There are only 2 references to the dropper left,
+        } else {
+            // The shards aren't adjacent, but there are only 2 references to the dropper left,
             // and we're holding ldropper and rdropper, so we can freely re-use the allocation
 
             let new_data = unsafe {
                 if rdata < ldata {
                     // If right is actually on the left side, we have to shuffle things around
-                    if llen < rlen {
+                    let gap = ldata.offset_from(rdata.add(rlen)) as usize;
+                    if gap >= llen {
+                        // There's room for l in between, so we can just move r out of its way
+                        //  ...  |------ r ------|    ...    |---- l ----|  ...
+                        ptr::copy(rdata.as_ptr(), rdata.add(llen).as_ptr(), rlen);
+                        //  ...  |-- ... --|------ r ------| |---- l ----|  ...
+                        ptr::copy_nonoverlapping(ldata.as_ptr(), rdata.as_ptr(), llen);
+                        //  ...  |---- l ----|------ r ------|  ...
+                        rdata
+                    } else if gap >= rlen {
+                        // Same thing the other way around: move l towards r, then put r behind it
+                        //  ...  |---- r ----|    ...    |------ l ------|  ...
+                        ptr::copy(ldata.as_ptr(), ldata.sub(rlen).as_ptr(), llen);
+                        //  ...  |---- r ----|  |------ l ------|-- ... --|  ...
+                        ptr::copy_nonoverlapping(
+                            rdata.as_ptr(),
+                            ldata.sub(rlen).add(llen).as_ptr(),
+                            rlen,
+                        );
+                        //  ...  ...   |------ l ------|---- r ----|  ...
+                        ldata.sub(rlen)
+                    } else if llen < rlen {
                         //  ...  |---------- r ----------| ... |------ l ------|
-                        ptr::copy(ldata, rdata.add(rlen), llen);
+                        ptr::copy(ldata.as_ptr(), rdata.add(rlen).as_ptr(), llen);
                         //  ...  |---------- r ----------|------ l ------|  ...
-                        slice::from_raw_parts_mut(rdata, rlen+llen).rotate_left(rlen);
+                        slice::from_raw_parts_mut(rdata.as_ptr(), rlen+llen).rotate_left(rlen);
                         //  ...  |------ l ------|---------- r ----------|  ...
                         rdata
                     } else {
                         //  ...  |------ r ------| ... |---------- l ----------|
-                        ptr::copy(rdata, ldata.sub(rlen), rlen);
+                        ptr::copy(rdata.as_ptr(), ldata.sub(rlen).as_ptr(), rlen);
                         //  ...   ...  |------ r ------|---------- l ----------|
-                        slice::from_raw_parts_mut(ldata.sub(rlen), rlen+llen).rotate_left(rlen);
+                        slice::from_raw_parts_mut(ldata.sub(rlen).as_ptr(), rlen+llen).rotate_left(rlen);
                         //  ...   ...  |---------- l ----------|------ r ------|
                         ldata.sub(rlen)
                     }
                 } else {
                     // Otherwise, just scootch it over
                     //  ...  |---------- l ----------|    ...  |------ r ------|
-                    ptr::copy(rdata, ldata.add(llen), rlen);
+                    ptr::copy(rdata.as_ptr(), ldata.add(llen).as_ptr(), rlen);
                     //  ...  |---------- l ----------|------ r ------|   ...
                     ldata
                 }
             };
+            // Everything behind us is free real estate now
+            ldropper.forget_drops();
+            let end = unsafe { ldropper.ptr.add(ldropper.capacity) };
+            let cap = unsafe { end.offset_from(new_data.add(llen + rlen)) } as usize;
             Ok(VecShard {
                 data: new_data,
                 len: llen + rlen,
+                cap,
                 dropper: ldropper,
-            })
-        } else {
-            Err(CantMerge {
-                reason: WouldAlloc::OtherShardsLeft,
-                left: VecShard {
-                    dropper: ldropper,
-                    data: ldata,
-                    len: llen,
-                },
-                right: VecShard {
-                    dropper: rdropper,
-                    data: rdata,
-                    len: rlen,
-                },
+                _marker: PhantomData,
             })
         }
     }
@@ -309,48 +2581,522 @@ impl<T> VecShard<T> {
     ///
     /// This will attempt an O(1) merge like `merge_inplace` but fall back to copying slices around
     /// within their allocation and possibly allocating a new Vec if needed.
+    ///
+    /// A new Vec is only allocated if the shards are from different allocations,
+    /// or if there are other shards left in their allocation that stop us from moving things around.
+    /// In particular, if `left` and `right` are the only two shards in their allocation, merging them
+    /// will always re-use it, no matter their order or how far apart they are.
+    ///
+    /// If either shard is empty, the other one is returned as is, without moving anything.
+    ///
+    /// However the elements get there, they are only ever moved, never cloned or dropped along the way.
+    /// So the merged shard holds every element of `left` and `right` exactly once, and dropping it
+    /// drops them in index order: first `left`'s elements, front to back, then `right`'s.
+    /// The same goes for all the other merge functions.
     pub fn merge(left: Self, right: Self) -> Self {
-        Self::merge_noalloc(left, right).unwrap_or_else(|err| {
-            let (_ldropper, ldata, llen) = err.left.into_raw_parts();
-            let (_rdropper, rdata, rlen) = err.right.into_raw_parts();
+        Self::merge_with_capacity(left, right, 0)
+    }
+
+    /// Merge the given shards into a single shard, allocating instead of moving a lot of elements around.
+    ///
+    /// This is `merge`, except for shards that `merge` would have to move around within their allocation,
+    /// that is when [`merge_cost`](crate::VecShard::merge_cost) is [`MergeCost::Move`].
+    /// If that would move more than `policy.max_move_bytes` worth of elements, they are copied
+    /// into a new Vec instead, leaving the old allocation to be freed.
+    ///
+    /// Whether that's faster depends on the size of the shards and on your allocator.
+    /// Rotating the elements into place takes a few passes over them, while copying them out takes a single one,
+    /// so with a warm allocator, allocating can win for shards in the low kilobytes.
+    /// Large allocations usually come straight from the OS though, and faulting in the fresh pages
+    /// makes allocating far slower than any in-place move. That's why `merge` never does it.
+    /// Measure before you pick a limit.
+    ///
+    /// ```
+    /// # use vecshard::{MergePolicy, ShardExt, VecShard};
+    /// let vec = vec![1, 2, 3, 4];
+    /// let base = vec.as_ptr();
+    /// let (left, right) = vec.split_inplace_at(1);
+    ///
+    /// let policy = MergePolicy { max_move_bytes: 8 };
+    /// let merged = VecShard::merge_with_policy(right, left, policy);
+    /// assert_eq!(*merged, [2, 3, 4, 1]);
+    /// assert_ne!(merged.as_ptr(), base);
+    /// ```
+    pub fn merge_with_policy(left: Self, right: Self, policy: MergePolicy) -> Self {
+        let bytes = (left.len + right.len).saturating_mul(mem::size_of::<T>());
+        if bytes > policy.max_move_bytes && Self::merge_cost(&left, &right) == MergeCost::Move {
+            let vec = Vec::with_capacity(left.len + right.len);
+            return Self::merge_into([left, right], vec);
+        }
+        Self::merge(left, right)
+    }
+
+    /// Merge two shards so that the result holds `first`'s elements followed by `second`'s.
+    ///
+    /// This is what `merge` does too, but spelled out so you don't have to rely on it:
+    /// no matter where the two shards are in memory, the logical order always comes from the arguments.
+    /// The cheapest way to get there is picked automatically:
+    ///
+    /// - if `second` directly follows `first`, they're merged in place in O(1)
+    /// - if `first` directly follows `second`, the elements are rotated into order without allocating
+    /// - otherwise, the elements are moved (within their allocation if possible) or copied into a new `Vec`
+    ///
+    /// ```
+    /// # use vecshard::{ShardExt, VecShard};
+    /// let (left, right) = vec![1, 2, 3, 4].split_inplace_at(2);
+    ///
+    /// assert_eq!(*VecShard::merge_ordered(right, left), [3, 4, 1, 2]);
+    /// ```
+    pub fn merge_ordered(first: Self, second: Self) -> Self {
+        Self::merge(first, second)
+    }
+
+    /// Merge three shards into a single shard holding `a`'s elements, then `b`'s, then `c`'s.
+    ///
+    /// This is the same as merging them two at a time, but it picks the order of the two merges
+    /// with [`merge_cost`](crate::VecShard::merge_cost) instead of always starting with `a` and `b`:
+    ///
+    /// - if one of the pairs can be merged in place, that one goes first, which may make room for the other merge
+    /// - if both pairs would need a new allocation, all three shards are copied into a single new Vec
+    ///   instead of allocating twice
+    /// - otherwise, the pair that can be merged by moving elements goes first
+    ///
+    /// Either way, the order of the elements is always `a`, `b`, `c`.
+    ///
+    /// ```
+    /// # use vecshard::{ShardExt, VecShard};
+    /// let vec = vec![1, 2, 3, 4, 5];
+    /// let base = vec.as_ptr();
+    ///
+    /// let (b, rest) = vec.split_inplace_at(2);
+    /// let (c, a) = rest.split_inplace_at(2);
+    ///
+    /// // merging a and b first would mean allocating, since c is in the way
+    /// let merged = VecShard::merge3(a, b, c);
+    /// assert_eq!(*merged, [5, 1, 2, 3, 4]);
+    /// assert_eq!(merged.as_ptr(), base);
+    /// ```
+    pub fn merge3(a: Self, b: Self, c: Self) -> Self {
+        use MergeCost::*;
+        match (Self::merge_cost(&a, &b), Self::merge_cost(&b, &c)) {
+            (InPlace, _) => Self::merge(Self::merge(a, b), c),
+            (_, InPlace) => Self::merge(a, Self::merge(b, c)),
+            (Allocate, Allocate) => {
+                let vec = Vec::with_capacity(a.len + b.len + c.len);
+                Self::merge_into([a, b, c], vec)
+            }
+            (Move, _) => Self::merge(Self::merge(a, b), c),
+            (Allocate, Move) => Self::merge(a, Self::merge(b, c)),
+        }
+    }
+
+    /// Merge two shards straight into a Vec holding `left`'s elements followed by `right`'s.
+    ///
+    /// This gives the same Vec as `Vec::from(VecShard::merge(left, right))`, but saves a pass over the elements
+    /// when `left` and `right` are the only two shards in their allocation. The merge would move them
+    /// next to each other somewhere in the allocation, and the conversion would then move them to its front.
+    /// This moves them into their final places at the front right away instead, re-using the allocation.
+    ///
+    /// In every other case, merging and converting already moves each element at most once,
+    /// so this does just that.
+    ///
+    /// ```
+    /// # use vecshard::{ShardExt, VecShard};
+    /// let vec = vec![1, 2, 3, 4, 5];
+    /// let base = vec.as_ptr();
+    ///
+    /// let (left, rest) = vec.split_inplace_at(1);
+    /// let (middle, right) = rest.split_inplace_at(2);
+    /// drop(middle);
+    ///
+    /// let vec = VecShard::join_into_vec(right, left);
+    /// assert_eq!(vec, [4, 5, 1]);
+    /// assert_eq!(vec.as_ptr(), base);
+    /// ```
+    pub fn join_into_vec(left: Self, right: Self) -> Vec<T> {
+        // See `into_vec_reporting` for why the refcount can be trusted
+        if mem::size_of::<T>() == 0
+            || left.len == 0
+            || right.len == 0
+            || !Refcount::ptr_eq(&left.dropper, &right.dropper)
+            || Refcount::strong_count(&left.dropper) != 2
+        {
+            return Self::merge(left, right).into();
+        }
+
+        let (loff, roff) = (left.offset(), right.offset());
+        let (ldropper, ldata, llen) = left.into_raw_parts();
+        let (rdropper, rdata, rlen) = right.into_raw_parts();
+        drop(rdropper);
+        let base = ldropper.ptr;
+
+        // Each shard gets moved at most once, so it must never be moved over the other one's elements
+        unsafe {
+            if loff < roff || llen <= roff {
+                // r isn't in the way of l's final place, so l can go first
+                ptr::copy(ldata.as_ptr(), base.as_ptr(), llen);
+                ptr::copy(rdata.as_ptr(), base.add(llen).as_ptr(), rlen);
+            } else if llen + rlen <= loff {
+                // r is in the way, but its own final place is free
+                ptr::copy(rdata.as_ptr(), base.add(llen).as_ptr(), rlen);
+                ptr::copy(ldata.as_ptr(), base.as_ptr(), llen);
+            } else {
+                // Too tight for that, so move both to the front as they are and rotate them into order
+                ptr::copy(rdata.as_ptr(), base.as_ptr(), rlen);
+                ptr::copy(ldata.as_ptr(), base.add(rlen).as_ptr(), llen);
+                slice::from_raw_parts_mut(base.as_ptr(), llen + rlen).rotate_left(rlen);
+            }
+        }
+
+        let dropper = Refcount::try_unwrap(ldropper)
+            .unwrap_or_else(|_| unreachable!("both shards' references are gone"));
+        let (ptr, capacity) = dropper.into_raw();
+        unsafe { Vec::from_raw_parts(ptr.as_ptr(), llen + rlen, capacity) }
+    }
+
+    /// Merge the given shards into a single shard, moving it to one end of the allocation if anything has to move anyway.
+    ///
+    /// If the shards can be merged in place, this does just that and `dir` doesn't matter.
+    /// Otherwise, if they're the only two shards in their allocation, the merged elements end up
+    /// at its front for [`Direction::Low`] or at its back for [`Direction::High`].
+    /// At the front, all the room behind the elements becomes spare capacity; at the back, there is none.
+    ///
+    /// If there are other shards left in the allocation, the elements are rotated into place or copied
+    /// into a new Vec just like with [`merge`](crate::VecShard::merge), and `dir` is ignored.
+    ///
+    /// ```
+    /// # use vecshard::{Direction, ShardExt, VecShard};
+    /// let vec = vec![1, 2, 3, 4, 5, 6];
+    /// let base = vec.as_ptr();
+    ///
+    /// let (left, rest) = vec.split_inplace_at(2);
+    /// let (middle, right) = rest.split_inplace_at(2);
+    /// drop(middle);
+    ///
+    /// let merged = VecShard::merge_toward(left, right, Direction::High);
+    /// assert_eq!(*merged, [1, 2, 5, 6]);
+    /// assert_eq!(merged.as_ptr(), base.wrapping_add(2));
+    /// ```
+    pub fn merge_toward(left: Self, right: Self, dir: Direction) -> Self {
+        let err = match Self::merge_inplace(left, right) {
+            Ok(shard) => return shard,
+            Err(err) => err,
+        };
+
+        let mut merged = Self::merge(err.left, err.right);
+        // See `into_vec_reporting` for why the refcount can be trusted
+        if Refcount::strong_count(&merged.dropper) == 1 {
+            match dir {
+                Direction::Low => merged.reclaim_capacity(),
+                Direction::High => merged.move_to_back(),
+            }
+        }
+        merged
+    }
+
+    /// Move the elements of this shard to the back of its allocation, which it must be the only shard in.
+    fn move_to_back(&mut self) {
+        debug_assert_eq!(Refcount::strong_count(&self.dropper), 1);
+        // ZSTs are everywhere and nowhere at once
+        if mem::size_of::<T>() == 0 {
+            return;
+        }
 
+        let dest = unsafe { self.dropper.ptr.add(self.dropper.capacity - self.len) };
+        if self.data != dest {
+            unsafe { ptr::copy(self.data.as_ptr(), dest.as_ptr(), self.len) };
+            self.data = dest;
+        }
+        self.cap = 0;
+        self.dropper.forget_drops();
+    }
+
+    /// Merge the given shards into a single shard, not caring about the order of the elements.
+    ///
+    /// If the shards are adjacent but passed in the reverse order, this just returns a shard
+    /// spanning both of them as they lie in memory, i.e. `right`'s elements followed by `left`'s.
+    /// Unlike `merge`, this doesn't need to rotate anything and so takes O(1) time.
+    /// In all other cases, this does the same as `merge`.
+    ///
+    /// Only use this if you don't care about the order of the elements in the merged shard.
+    pub fn merge_swapped(left: Self, right: Self) -> Self {
+        match Self::merge_inplace(left, right) {
+            Ok(shard) => shard,
+            Err(CantMerge {
+                left,
+                right,
+                reason: WouldMove::WrongOrder,
+            }) => Self::merge_inplace(right, left).unwrap_or_else(|_| {
+                unreachable!("reverse-adjacent shards must merge the other way around")
+            }),
+            Err(err) => Self::merge(err.left, err.right),
+        }
+    }
+
+    /// Merge the given shards into a single shard, leaving room for `additional` more elements
+    /// if a new Vec has to be allocated.
+    ///
+    /// This behaves exactly like `merge`, except that the allocating fallback sizes the new Vec
+    /// to hold at least `left.len() + right.len() + additional` elements.
+    /// If the shards can be merged without allocating, the hint is ignored.
+    pub fn merge_with_capacity(left: Self, right: Self, additional: usize) -> Self {
+        Self::merge_noalloc(left, right).unwrap_or_else(|err| {
             // Give up and allocate
-            let mut vec = Vec::with_capacity(llen + rlen);
+            let vec = Vec::with_capacity(err.left.len + err.right.len + additional);
+            Self::merge_into([err.left, err.right], vec)
+        })
+    }
+
+    /// Merge the given shards into a single shard, but return an error instead of aborting
+    /// if a new Vec is needed and it can't be allocated.
+    ///
+    /// On error, you get both shards back, untouched.
+    pub fn try_merge(left: Self, right: Self) -> Result<Self, (TryReserveError, Self, Self)> {
+        let err = match Self::merge_noalloc(left, right) {
+            Ok(shard) => return Ok(shard),
+            Err(err) => err,
+        };
+
+        let mut vec = Vec::new();
+        match vec.try_reserve_exact(err.left.len + err.right.len) {
+            Ok(()) => Ok(Self::merge_into([err.left, err.right], vec)),
+            Err(e) => Err((e, err.left, err.right)),
+        }
+    }
+
+    /// Move the elements of all shards into `vec`, which must be empty and have enough capacity for all of them.
+    fn merge_into<const N: usize>(shards: [Self; N], mut vec: Vec<T>) -> Self {
+        debug_assert!(vec.is_empty() && vec.capacity() >= shards.iter().map(|s| s.len).sum());
+
+        for shard in shards {
+            let (_dropper, data, len) = shard.into_raw_parts();
+            unsafe {
+                ptr::copy(data.as_ptr(), vec.as_mut_ptr().add(vec.len()), len);
+                vec.set_len(vec.len() + len);
+            }
+        }
+        Self::from(vec)
+    }
+
+    /// Merge two sorted shards into a single sorted shard.
+    ///
+    /// Both `left` and `right` must already be sorted. If they aren't, the elements
+    /// will still all be there, but in an unspecified order.
+    /// The merge is stable, so of two equal elements, the one from `left` comes first.
+    ///
+    /// The shards are first merged just like with [`merge`](crate::VecShard::merge), so this
+    /// doesn't allocate whenever that doesn't. The two sorted runs are then merged in place using
+    /// rotations, which also doesn't need any extra memory and takes O(n log n) time in the worst case.
+    ///
+    /// ```
+    /// # use vecshard::{ShardExt, VecShard};
+    /// let (left, right) = vec![1, 4, 9, 2, 3, 10].split_inplace_at(3);
+    ///
+    /// assert_eq!(*VecShard::merge_sorted(left, right), [1, 2, 3, 4, 9, 10]);
+    /// ```
+    pub fn merge_sorted(left: Self, right: Self) -> Self
+    where
+        T: Ord,
+    {
+        let mid = left.len;
+        let mut merged = Self::merge(left, right);
+        merge_sorted_runs(&mut merged, mid);
+        merged
+    }
+
+    /// Concatenate the given shards into a single shard, putting a clone of `sep` between each of them.
+    ///
+    /// This is the owning counterpart to `slice::join`.
+    /// If one of the shards is the only one left in its allocation and that allocation is big enough
+    /// to hold the result, the result is built in there instead of in a new Vec.
+    /// If there are several, the biggest one is used.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let parts: Vec<_> = vec!["a", "b", "c"].into_iter().map(|s| VecShard::from(Vec::from(s))).collect();
+    ///
+    /// assert_eq!(VecShard::join_owned(parts, b'/').as_str(), Ok("a/b/c"));
+    /// ```
+    pub fn join_owned(mut parts: Vec<Self>, sep: T) -> Self
+    where
+        T: Clone,
+    {
+        match parts.len() {
+            0 => return Self::new(),
+            1 => return parts.pop().unwrap(),
+            _ => {}
+        }
+
+        let total = parts.iter().map(|p| p.len).sum::<usize>() + parts.len() - 1;
+        // Clone all the separators up front, so nothing can panic while we're moving stuff around
+        let mut seps = vec![sep; parts.len() - 1].into_iter();
+
+        let host = parts
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| Refcount::strong_count(&p.dropper) == 1 && p.dropper.capacity >= total)
+            .max_by_key(|(_, p)| p.dropper.capacity)
+            .map(|(i, _)| i);
+
+        let vec = if let Some(i) = host {
+            let mut after = parts.split_off(i);
+            let host: Vec<T> = after.remove(0).into();
+            let before = parts;
+
+            let hlen = host.len();
+            let prefix = before.iter().map(|p| p.len + 1).sum::<usize>();
+
+            // The host is the only shard in its allocation, so none of the others can overlap with it
+            let mut vec = host;
             unsafe {
-                ptr::copy(ldata, vec.as_mut_ptr(), llen);
-                ptr::copy(rdata, vec.as_mut_ptr().add(llen), rlen);
-                vec.set_len(llen + rlen);
+                vec.set_len(0);
+                let base = vec.as_mut_ptr();
+                // make room in front of the host's elements
+                ptr::copy(base, base.add(prefix), hlen);
+
+                let mut pos = 0;
+                for part in before {
+                    let (_dropper, data, len) = part.into_raw_parts();
+                    ptr::copy_nonoverlapping(data.as_ptr(), base.add(pos), len);
+                    base.add(pos + len).write(seps.next().unwrap());
+                    pos += len + 1;
+                }
+                pos += hlen;
+                for part in after {
+                    base.add(pos).write(seps.next().unwrap());
+                    let (_dropper, data, len) = part.into_raw_parts();
+                    ptr::copy_nonoverlapping(data.as_ptr(), base.add(pos + 1), len);
+                    pos += len + 1;
+                }
+                vec.set_len(total);
             }
-            Self::from(vec)
+            vec
+        } else {
+            // No luck, just allocate
+            let mut vec = Vec::with_capacity(total);
+            let mut parts = parts.into_iter();
+            vec.extend(parts.next().unwrap());
+            for (part, sep) in parts.zip(seps) {
+                vec.push(sep);
+                vec.extend(part);
+            }
+            vec
+        };
+
+        Self::from(vec)
+    }
+
+    /// Concatenate a stream of shards into a single shard, merging them from left to right.
+    ///
+    /// As long as each shard directly follows the previous one in the same allocation,
+    /// they're merged in place in O(1). Whenever that's not the case, the elements collected so far
+    /// are moved into a `Vec` of their own, which then grows just like with `Vec::extend`,
+    /// so a stream of unrelated shards only takes amortized O(n) time overall.
+    ///
+    /// If the iterator is empty, so is the result.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let chunks = VecShard::from(vec![1, 2, 3, 4, 5]).into_shard_chunks(2);
+    /// let others = vec![VecShard::from(vec![6]), VecShard::from(vec![7, 8])];
+    ///
+    /// assert_eq!(*VecShard::flatten_shards(chunks.chain(others)), [1, 2, 3, 4, 5, 6, 7, 8]);
+    /// ```
+    pub fn flatten_shards<I: IntoIterator<Item = Self>>(iter: I) -> Self {
+        let mut iter = iter.into_iter();
+        let first = match iter.next() {
+            Some(shard) => shard,
+            None => return Self::new(),
+        };
+
+        iter.fold(first, |acc, shard| {
+            Self::merge_inplace(acc, shard).unwrap_or_else(|err| {
+                // If acc has a Vec of its own by now, this re-uses it
+                let mut vec = Vec::from(err.left);
+                vec.extend(err.right);
+                Self::from(vec)
+            })
         })
     }
 }
 
+/// Stably merge the sorted runs `v[..mid]` and `v[mid..]` in place.
+///
+/// This splits both runs at a matching point, rotates the middle parts past each other
+/// and does the same for both halves, the same way the classic `std::inplace_merge` does without a buffer.
+fn merge_sorted_runs<T: Ord>(v: &mut [T], mid: usize) {
+    let len = v.len();
+    if mid == 0 || mid == len {
+        return;
+    }
+    if len == 2 {
+        if v[1] < v[0] {
+            v.swap(0, 1);
+        }
+        return;
+    }
+
+    let (first_cut, second_cut) = if mid >= len - mid {
+        // cut the left run in half, and the right one in front of the first element that's not smaller
+        let first_cut = mid / 2;
+        let pivot = &v[first_cut];
+        (first_cut, mid + v[mid..].partition_point(|x| x < pivot))
+    } else {
+        // cut the right run in half, and the left one in front of the first element that's bigger
+        let second_cut = mid + (len - mid) / 2;
+        let pivot = &v[second_cut];
+        (v[..mid].partition_point(|x| x <= pivot), second_cut)
+    };
+
+    v[first_cut..second_cut].rotate_left(mid - first_cut);
+    let new_mid = first_cut + (second_cut - mid);
+
+    let (front, back) = v.split_at_mut(new_mid);
+    merge_sorted_runs(front, first_cut);
+    merge_sorted_runs(back, second_cut - new_mid);
+}
+
 impl<T> ShardExt for VecShard<T> {
     type Shard = VecShard<T>;
 
-    fn split_inplace_at(mut self, at: usize) -> (Self::Shard, Self::Shard) {
+    #[track_caller]
+    fn split_inplace_at(self, at: usize) -> (Self::Shard, Self::Shard) {
         assert!(at <= self.len);
 
-        let right = VecShard {
-            dropper: self.dropper.clone(),
-            data: unsafe { self.data.add(at) },
-            len: self.len - at,
-        };
+        unsafe { self.split_inplace_at_unchecked(at) }
+    }
 
-        // for the left shard, just cut ourselves down to size
-        self.len = at;
+    #[track_caller]
+    fn split_inplace_into(mut self, n: usize) -> Vec<Self::Shard> {
+        assert!(n > 0);
 
-        (self, right)
+        let (base, extra) = (self.len / n, self.len % n);
+        let mut shards = Vec::with_capacity(n);
+        for i in 1..n {
+            shards.push(self.take_shard(base + (i <= extra) as usize));
+        }
+        // the last one keeps the spare capacity
+        shards.push(self);
+        shards
+    }
+}
+
+/// An empty shard, see [`VecShard::new`](crate::VecShard::new).
+impl<T> Default for VecShard<T> {
+    fn default() -> Self {
+        VecShard::new()
     }
 }
 
 impl<T> Drop for VecShard<T> {
     fn drop(&mut self) {
+        let start = self.offset();
+        self.dropper.track_drop(start..start + self.len);
+
         // Drop all the elements
         // The VecDropper will take care of freeing the Vec itself, if needed
         for o in 0..self.len {
-            unsafe { ptr::drop_in_place(self.data.add(o)) };
+            unsafe { ptr::drop_in_place(self.data.add(o).as_ptr()) };
         }
     }
 }
@@ -359,19 +3105,19 @@ impl<T> Deref for VecShard<T> {
     type Target = [T];
 
     fn deref(&self) -> &[T] {
-        unsafe { slice::from_raw_parts(self.data, self.len) }
+        unsafe { slice::from_raw_parts(self.data.as_ptr(), self.len) }
     }
 }
 
 impl<T> DerefMut for VecShard<T> {
     fn deref_mut(&mut self) -> &mut [T] {
-        unsafe { slice::from_raw_parts_mut(self.data, self.len) }
+        unsafe { slice::from_raw_parts_mut(self.data.as_ptr(), self.len) }
     }
 }
 
 impl<T> AsRef<[T]> for VecShard<T> {
     fn as_ref(&self) -> &[T] {
-        &*self
+        self
     }
 }
 
@@ -383,7 +3129,7 @@ impl<T> AsMut<[T]> for VecShard<T> {
 
 impl<T> Borrow<[T]> for VecShard<T> {
     fn borrow(&self) -> &[T] {
-        &*self
+        self
     }
 }
 
@@ -396,12 +3142,14 @@ impl<T> BorrowMut<[T]> for VecShard<T> {
 impl<T, I: SliceIndex<[T]>> Index<I> for VecShard<T> {
     type Output = <I as slice::SliceIndex<[T]>>::Output;
 
+    #[track_caller]
     fn index(&self, idx: I) -> &Self::Output {
         &((**self)[idx])
     }
 }
 
 impl<T, I: SliceIndex<[T]>> IndexMut<I> for VecShard<T> {
+    #[track_caller]
     fn index_mut(&mut self, idx: I) -> &mut Self::Output {
         &mut ((**self)[idx])
     }
@@ -444,6 +3192,8 @@ impl<T> DoubleEndedIterator for VecShard<T> {
     fn next_back(&mut self) -> Option<T> {
         if self.len > 0 {
             self.len -= 1;
+            // the slot we just emptied is ours to reuse
+            self.cap += 1;
             Some(unsafe { self.data.add(self.len).read() })
         } else {
             None
@@ -461,65 +3211,143 @@ impl<T: Hash> Hash for VecShard<T> {
 
 impl<T> From<Vec<T>> for VecShard<T> {
     fn from(mut v: Vec<T>) -> Self {
+        // Nothing to free, so share the bookkeeping with the other empty shards
+        // (a Vec of ZSTs always claims to have room for `usize::MAX` of them, so it doesn't end up here)
+        if v.capacity() == 0 {
+            return VecShard::new();
+        }
+
+        // Vec's pointer is never null, even if it hasn't allocated anything
+        // If it hasn't, it's dangling, but shards of an empty Vec only ever offset it by 0, which is fine
+        let ptr = unsafe { NonNull::new_unchecked(v.as_mut_ptr()) };
         let res = VecShard {
-            dropper: Arc::new(VecDropper {
-                ptr: v.as_mut_ptr(),
-                capacity: v.capacity(),
-            }),
-            data: v.as_mut_ptr(),
+            dropper: Refcount::new(VecDropper::new(ptr, v.capacity(), v.len())),
+            data: ptr,
             len: v.len(),
+            cap: v.capacity() - v.len(),
+            _marker: PhantomData,
         };
         mem::forget(v);
         res
     }
 }
 
-impl<T> Into<Vec<T>> for VecShard<T> {
-    fn into(self) -> Vec<T> {
-        // First, move everything out of self so we don't drop anything
-        let (dropper, data, len) = self.into_raw_parts();
+/// A `VecDeque` may be wrapped around its ring buffer, so this first has to make it contiguous,
+/// which may mean moving its elements around once. The allocation itself is re-used.
+impl<T> From<VecDeque<T>> for VecShard<T> {
+    fn from(deque: VecDeque<T>) -> Self {
+        VecShard::from(Vec::from(deque))
+    }
+}
 
-        // Optimization: if this shard is the only one left from the backing Vec, we re-use its allocation
-        if let Ok(dropper) = Arc::try_unwrap(dropper) {
-            // If our data is already at the start of the backing Vec, we don't need to move it
-            if data != dropper.ptr {
-                unsafe { ptr::copy(data, dropper.ptr, len) };
-            }
-            let v = unsafe { Vec::from_raw_parts(dropper.ptr, len, dropper.capacity) };
-            // Make sure we don't drop anything that the new Vec will need
-            mem::forget(dropper);
-            v
-        } else {
-            // Otherwise, just allocate a new Vec
-            let mut v = Vec::with_capacity(len);
-            unsafe {
-                ptr::copy_nonoverlapping(data, v.as_mut_ptr(), len);
-                v.set_len(len);
-            };
-            v
+/// This re-uses the boxed slice's allocation, so nothing is copied.
+/// A boxed slice doesn't have any spare room though, so the shard's capacity is exactly its length
+/// and the first [`push`](crate::VecShard::push) will have to reallocate.
+impl<T> From<Box<[T]>> for VecShard<T> {
+    fn from(boxed: Box<[T]>) -> Self {
+        VecShard::from(Vec::from(boxed))
+    }
+}
+
+impl<T> From<VecShard<T>> for Vec<T> {
+    fn from(shard: VecShard<T>) -> Self {
+        shard.into_vec_reporting().0
+    }
+}
+
+/// This moves the elements out into the array and fails if the shard doesn't hold exactly `N` of them.
+///
+/// ```
+/// # use std::convert::TryFrom;
+/// # use vecshard::ShardExt;
+/// let (left, right) = vec![1, 2, 3, 4, 5].split_inplace_at(2);
+///
+/// assert_eq!(<[i32; 2]>::try_from(left).unwrap(), [1, 2]);
+///
+/// let err = <[i32; 2]>::try_from(right).unwrap_err();
+/// assert_eq!(*err.shard, [3, 4, 5]);
+/// ```
+impl<T, const N: usize> TryFrom<VecShard<T>> for [T; N] {
+    type Error = ConversionError<T>;
+
+    fn try_from(shard: VecShard<T>) -> Result<Self, Self::Error> {
+        if shard.len != N {
+            return Err(ConversionError {
+                reason: ConversionFailure::WrongLength {
+                    expected: N,
+                    actual: shard.len,
+                },
+                shard,
+            });
         }
+
+        // The elements are moved out, the dropper only frees the allocation
+        let (_dropper, data, _) = shard.into_raw_parts();
+        Ok(unsafe { ptr::read(data.as_ptr() as *const [T; N]) })
     }
 }
 
+/// Cloning copies the elements into a new allocation of exactly `len` elements.
+///
+/// For `Copy` types, this is a single `memcpy`, just like cloning a Vec, since `extend_from_slice` takes care of that.
 impl<T: Clone> Clone for VecShard<T> {
     fn clone(&self) -> VecShard<T> {
         // Not much we can do here, just make a new Vec
         let mut vec = Vec::with_capacity(self.len);
-        vec.extend_from_slice(unsafe { slice::from_raw_parts(self.data, self.len) });
+        vec.extend_from_slice(unsafe { slice::from_raw_parts(self.data.as_ptr(), self.len) });
         VecShard::from(vec)
     }
 }
 
+/// By default, a shard is formatted just like a slice.
+///
+/// The alternate form (`{:#?}`) additionally shows where the shard lives in its backing allocation
+/// and how many shards share it, which comes in handy when figuring out why a merge didn't work out.
 impl<T: fmt::Debug> fmt::Debug for VecShard<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:?}", &**self)
+        if f.alternate() {
+            f.debug_struct("VecShard")
+                .field("data", &&**self)
+                .field("offset", &self.offset())
+                .field("len", &self.len)
+                .field("backing_capacity", &self.dropper.capacity)
+                .field("shard_count", &self.shard_count())
+                .finish()
+        } else {
+            write!(f, "{:?}", &**self)
+        }
+    }
+}
+
+/// The elements of a shard joined by a separator, see [`VecShard::display`].
+struct Joined<'a, T> {
+    items: &'a [T],
+    sep: &'a str,
+}
+
+impl<T: fmt::Display> fmt::Display for Joined<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, item) in self.items.iter().enumerate() {
+            if i > 0 {
+                f.write_str(self.sep)?;
+            }
+            // Passing `f` on keeps the caller's flags
+            fmt::Display::fmt(item, f)?;
+        }
+        Ok(())
     }
 }
 
 impl<T> ShardExt for Vec<T> {
     type Shard = VecShard<T>;
 
+    #[track_caller]
     fn split_inplace_at(self, at: usize) -> (Self::Shard, Self::Shard) {
         VecShard::from(self).split_inplace_at(at)
     }
+
+    #[track_caller]
+    fn split_inplace_into(self, n: usize) -> Vec<Self::Shard> {
+        VecShard::from(self).split_inplace_into(n)
+    }
 }
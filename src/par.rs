@@ -0,0 +1,83 @@
+use crate::{ShardExt, VecShard};
+
+/// Below this many elements, `par_sort` and `par_fold` just work sequentially.
+const SEQUENTIAL_LEN: usize = 4096;
+
+impl<T: Ord + Send> VecShard<T> {
+    /// Sort this shard with a parallel mergesort.
+    ///
+    /// The shard is split in half over and over, the halves are sorted on rayon's thread pool
+    /// and then put back together with [`merge_sorted`](crate::VecShard::merge_sorted).
+    /// Since the halves always come from the same shard, everything stays in the original allocation.
+    ///
+    /// Like [`slice::sort`], this sort is stable.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let shard = VecShard::from(vec![5, 2, 9, 1, 7]);
+    ///
+    /// assert_eq!(*shard.par_sort(), [1, 2, 5, 7, 9]);
+    /// ```
+    pub fn par_sort(mut self) -> Self {
+        if self.len <= SEQUENTIAL_LEN {
+            self.sort();
+            return self;
+        }
+
+        let mid = self.len / 2;
+        let (left, right) = self.split_inplace_at(mid);
+        let (left, right) = rayon::join(|| left.par_sort(), || right.par_sort());
+        VecShard::merge_sorted(left, right)
+    }
+}
+
+impl<T: Send> VecShard<T> {
+    /// Fold the elements of this shard in parallel, consuming them.
+    ///
+    /// The shard is split in half over and over, each piece is folded on rayon's thread pool,
+    /// starting from a fresh `identity()`, and the partial results are put back together with `combine`.
+    /// Splitting a shard is O(1), so this doesn't need any of the machinery of rayon's parallel iterators.
+    ///
+    /// Partial results are always combined in order, with the left one first,
+    /// but how the elements are grouped depends on the length of the shard.
+    /// So `combine` has to be associative, and `identity()` has to be a neutral element for it,
+    /// otherwise the result is unpredictable.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let shard = VecShard::from((1..=10_000u64).collect::<Vec<_>>());
+    ///
+    /// let sum = shard.par_fold(|| 0, |acc, x| acc + x, |a, b| a + b);
+    /// assert_eq!(sum, 50_005_000);
+    /// ```
+    pub fn par_fold<A, ID, F, C>(self, identity: ID, fold: F, combine: C) -> A
+    where
+        A: Send,
+        ID: Fn() -> A + Sync,
+        F: Fn(A, T) -> A + Sync,
+        C: Fn(A, A) -> A + Sync,
+    {
+        par_fold_pieces(self, &identity, &fold, &combine)
+    }
+}
+
+fn par_fold_pieces<T, A, ID, F, C>(shard: VecShard<T>, identity: &ID, fold: &F, combine: &C) -> A
+where
+    T: Send,
+    A: Send,
+    ID: Fn() -> A + Sync,
+    F: Fn(A, T) -> A + Sync,
+    C: Fn(A, A) -> A + Sync,
+{
+    if shard.len <= SEQUENTIAL_LEN {
+        return Iterator::fold(shard, identity(), fold);
+    }
+
+    let mid = shard.len / 2;
+    let (left, right) = shard.split_inplace_at(mid);
+    let (left, right) = rayon::join(
+        || par_fold_pieces(left, identity, fold, combine),
+        || par_fold_pieces(right, identity, fold, combine),
+    );
+    combine(left, right)
+}
@@ -1,10 +1,13 @@
 use crate::VecShard;
 
 use serde::{
-    de::{Deserialize, Deserializer},
-    ser::{Serialize, SerializeSeq, Serializer},
+    de::{Deserialize, DeserializeSeed, Deserializer, Error, SeqAccess, Visitor},
+    ser::{Serialize, SerializeSeq, SerializeTuple, Serializer},
 };
+use std::{fmt, marker::PhantomData};
 
+/// A shard is serialized as a sequence of its own elements in order, just like a slice.
+/// Its siblings and the rest of the backing allocation are never part of the output.
 impl<T> Serialize for VecShard<T>
 where
     T: Serialize,
@@ -26,6 +29,98 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for VecShard<T> {
     where
         D: Deserializer<'de>,
     {
-        <Vec<T> as Deserialize>::deserialize::<D>(deserializer).map(|v| VecShard::from(v))
+        <Vec<T> as Deserialize>::deserialize::<D>(deserializer).map(VecShard::from)
+    }
+}
+
+/// A wrapper that (de)serializes a [`VecShard`](crate::VecShard) as a tuple instead of a sequence.
+///
+/// Some binary formats write a length prefix for sequences but not for tuples,
+/// so this can save a few bytes per shard.
+/// The flip side is that the length is not part of the output, so the reading side has to know it
+/// up front. That's why this doesn't implement `Deserialize`: pass the length to [`AsTuple::seed`]
+/// and deserialize through the seed instead.
+/// Only prefer this over the default representation if you already store the length somewhere else.
+///
+/// ```
+/// # use bincode::Options;
+/// # use serde::de::DeserializeSeed;
+/// # use vecshard::{AsTuple, VecShard};
+/// let options = bincode::DefaultOptions::new().with_fixint_encoding();
+/// let bytes = options.serialize(&AsTuple(VecShard::from(vec![1u16, 2, 3]))).unwrap();
+/// // no length prefix, just the elements
+/// assert_eq!(bytes, [1, 0, 2, 0, 3, 0]);
+///
+/// let mut de = bincode::Deserializer::from_slice(&bytes, options);
+/// let AsTuple(shard) = AsTuple::<u16>::seed(3).deserialize(&mut de).unwrap();
+/// assert_eq!(*shard, [1, 2, 3]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsTuple<T>(pub VecShard<T>);
+
+impl<T> AsTuple<T> {
+    /// Get a seed that deserializes a tuple of exactly `len` elements into an `AsTuple`.
+    pub fn seed(len: usize) -> TupleSeed<T> {
+        TupleSeed {
+            len,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Serialize for AsTuple<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut tup = serializer.serialize_tuple(self.0.len)?;
+        for e in &*self.0 {
+            tup.serialize_element(&e)?;
+        }
+        tup.end()
+    }
+}
+
+/// Deserializes an [`AsTuple`](crate::AsTuple) of a known length, see [`AsTuple::seed`].
+#[derive(Debug, Clone, Copy)]
+pub struct TupleSeed<T> {
+    len: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<'de, T: Deserialize<'de>> DeserializeSeed<'de> for TupleSeed<T> {
+    type Value = AsTuple<T>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple(self.len, self)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for TupleSeed<T> {
+    type Value = AsTuple<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a tuple of {} elements", self.len)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        // The length may come from untrusted input, so don't reserve too much up front
+        let mut vec = Vec::with_capacity(self.len.min(4096));
+        for i in 0..self.len {
+            match seq.next_element()? {
+                Some(e) => vec.push(e),
+                None => return Err(A::Error::invalid_length(i, &self)),
+            }
+        }
+        Ok(AsTuple(VecShard::from(vec)))
     }
 }
@@ -0,0 +1,192 @@
+//! Byte shards as length-prefixed blobs.
+//!
+//! Serializing a `VecShard<u8>` the normal way goes through serde one element at a time,
+//! which many formats turn into one length-prefixed sequence of integers. The functions in here
+//! treat the shard as a single blob of bytes instead.
+//!
+//! [`serialize`] and [`deserialize`] hand the bytes to the format with `serialize_bytes`,
+//! so the format picks the framing. Use them with `#[serde(with = "vecshard::serde_layout")]`.
+//! For `bincode`'s default configuration, that means a little-endian `u64` length followed by the bytes,
+//! the same layout [`encode`] produces with [`LengthPrefix::U64`].
+//!
+//! [`encode`] and [`decode`] do the framing themselves, for custom protocols without a serde format.
+//! The wire format is the length of the payload in bytes as a little-endian `u32` or `u64`
+//! (see [`LengthPrefix`]), followed by the payload itself. Decoding splits the payload off the
+//! front of the buffer in O(1) without copying it.
+
+use crate::{ShardExt, VecShard};
+use serde::{
+    de::{Deserializer, Visitor},
+    ser::Serializer,
+};
+use std::{convert::TryFrom, error::Error, fmt};
+
+/// Serialize a byte shard as a single blob of bytes.
+pub fn serialize<S: Serializer>(shard: &VecShard<u8>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bytes(shard)
+}
+
+/// Deserialize a byte shard from a blob of bytes.
+///
+/// If the format hands over an owned buffer, it is re-used for the shard.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<VecShard<u8>, D::Error> {
+    deserializer.deserialize_byte_buf(BytesVisitor)
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = VecShard<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a byte array")
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(VecShard::from(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(VecShard::from(v))
+    }
+
+    // Some formats don't have a byte type of their own and pass a sequence instead
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut vec = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+        while let Some(b) = seq.next_element()? {
+            vec.push(b);
+        }
+        Ok(VecShard::from(vec))
+    }
+}
+
+/// The width of the length field in front of the payload. It is always little-endian.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum LengthPrefix {
+    U32,
+    U64,
+}
+
+impl LengthPrefix {
+    /// How many bytes the length field takes up.
+    pub fn width(self) -> usize {
+        match self {
+            LengthPrefix::U32 => 4,
+            LengthPrefix::U64 => 8,
+        }
+    }
+}
+
+/// A reason why a blob couldn't be encoded or decoded.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum LayoutError {
+    /// The buffer ends before the length field or the payload does.
+    Truncated { needed: u64, available: usize },
+    /// The length is larger than the length field or the limit allows.
+    TooLong { len: u64, max: u64 },
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LayoutError::Truncated { needed, available } => write!(
+                f,
+                "the blob is truncated: needed {} bytes, but only {} are available.",
+                needed, available
+            ),
+            LayoutError::TooLong { len, max } => write!(
+                f,
+                "the blob is {} bytes long, but at most {} are allowed.",
+                len, max
+            ),
+        }
+    }
+}
+
+impl Error for LayoutError {}
+
+/// Encode `bytes` as a length-prefixed blob.
+///
+/// Fails if the length doesn't fit into the length field.
+///
+/// ```
+/// # use vecshard::serde_layout::{encode, LengthPrefix};
+/// assert_eq!(encode(b"hi", LengthPrefix::U32).unwrap(), [2, 0, 0, 0, b'h', b'i']);
+/// ```
+pub fn encode(bytes: &[u8], prefix: LengthPrefix) -> Result<Vec<u8>, LayoutError> {
+    let len = bytes.len() as u64;
+    let mut out = Vec::with_capacity(prefix.width() + bytes.len());
+    match prefix {
+        LengthPrefix::U32 => {
+            let len = u32::try_from(len).map_err(|_| LayoutError::TooLong {
+                len,
+                max: u64::from(u32::MAX),
+            })?;
+            out.extend_from_slice(&len.to_le_bytes());
+        }
+        LengthPrefix::U64 => out.extend_from_slice(&len.to_le_bytes()),
+    }
+    out.extend_from_slice(bytes);
+    Ok(out)
+}
+
+/// Split a length-prefixed blob off the front of `buf`.
+///
+/// On success, you get the payload (without the length field) and the rest of the buffer,
+/// both still sharing `buf`'s allocation. Payloads longer than `max_len` are rejected
+/// without looking any further, so a bogus length field can't make you wait for gigabytes.
+///
+/// If the blob isn't complete yet or is too long, you get `buf` back untouched along with the reason.
+///
+/// ```
+/// # use vecshard::{VecShard, serde_layout::{decode, LengthPrefix}};
+/// let buf = VecShard::from(vec![2, 0, 0, 0, b'h', b'i', 5]);
+///
+/// let (payload, rest) = decode(buf, LengthPrefix::U32, 1024).unwrap();
+/// assert_eq!(*payload, *b"hi");
+/// assert_eq!(*rest, [5]);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn decode(
+    buf: VecShard<u8>,
+    prefix: LengthPrefix,
+    max_len: usize,
+) -> Result<(VecShard<u8>, VecShard<u8>), (LayoutError, VecShard<u8>)> {
+    let width = prefix.width();
+    if buf.len() < width {
+        let err = LayoutError::Truncated {
+            needed: width as u64,
+            available: buf.len(),
+        };
+        return Err((err, buf));
+    }
+
+    let len = match prefix {
+        LengthPrefix::U32 => u64::from(u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]])),
+        LengthPrefix::U64 => {
+            let mut field = [0; 8];
+            field.copy_from_slice(&buf[..8]);
+            u64::from_le_bytes(field)
+        }
+    };
+    if len > max_len as u64 {
+        let err = LayoutError::TooLong {
+            len,
+            max: max_len as u64,
+        };
+        return Err((err, buf));
+    }
+    if len > (buf.len() - width) as u64 {
+        let err = LayoutError::Truncated {
+            needed: (width as u64).saturating_add(len),
+            available: buf.len(),
+        };
+        return Err((err, buf));
+    }
+
+    // fits into the buffer now, so it also fits into a usize
+    let needed = width + len as usize;
+    let (frame, rest) = buf.split_inplace_at(needed);
+    let (_, payload) = frame.split_inplace_at(width);
+    Ok((payload, rest))
+}
@@ -0,0 +1,97 @@
+use crate::VecShard;
+use std::{
+    borrow::Borrow,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+    sync::Arc,
+};
+
+/// A read-only shard that can be cheaply shared between multiple owners.
+///
+/// Cloning a [`VecShard`] copies all of its elements. Sometimes, you only need to read the elements
+/// from several places (e.g. several threads) though, and a `SharedShard` lets you do just that:
+/// cloning it only bumps a reference count, and it derefs to a slice so you can read it as usual.
+///
+/// ```
+/// # use vecshard::{SharedShard, VecShard};
+/// let shared = SharedShard::from(VecShard::from(vec![1, 2, 3]));
+/// let other_owner = shared.clone();
+///
+/// assert_eq!(*other_owner, [1, 2, 3]);
+/// assert_eq!(shared.as_ptr(), other_owner.as_ptr());
+/// ```
+pub struct SharedShard<T>(Arc<VecShard<T>>);
+
+impl<T> SharedShard<T> {
+    /// Get the shard back, if this is the only owner left.
+    ///
+    /// Otherwise, you get the `SharedShard` back as the `Err`.
+    pub fn try_unwrap(self) -> Result<VecShard<T>, Self> {
+        Arc::try_unwrap(self.0).map_err(SharedShard)
+    }
+
+    /// Get the underlying `Arc`.
+    pub fn into_arc(self) -> Arc<VecShard<T>> {
+        self.0
+    }
+}
+
+impl<T> From<VecShard<T>> for SharedShard<T> {
+    fn from(shard: VecShard<T>) -> Self {
+        SharedShard(Arc::new(shard))
+    }
+}
+
+impl<T> From<Arc<VecShard<T>>> for SharedShard<T> {
+    fn from(shard: Arc<VecShard<T>>) -> Self {
+        SharedShard(shard)
+    }
+}
+
+/// This only bumps the reference count, the elements stay where they are.
+impl<T> Clone for SharedShard<T> {
+    fn clone(&self) -> Self {
+        SharedShard(self.0.clone())
+    }
+}
+
+impl<T> Deref for SharedShard<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T> AsRef<[T]> for SharedShard<T> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T> Borrow<[T]> for SharedShard<T> {
+    fn borrow(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T: PartialEq> PartialEq for SharedShard<T> {
+    fn eq(&self, rhs: &Self) -> bool {
+        **self == **rhs
+    }
+}
+
+impl<T: Eq> Eq for SharedShard<T> {}
+
+impl<T: Hash> Hash for SharedShard<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SharedShard<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{
+    error::{ConversionError, ConversionFailure},
+    iter::Frames,
+    ShardExt, VecShard,
+};
+use std::{
+    borrow::Cow,
+    io,
+    str::{self, Utf8Error},
+    string::FromUtf16Error,
+};
+
+impl VecShard<u8> {
+    /// View the bytes of this shard as a string slice.
+    ///
+    /// Since a split can happen anywhere, a shard may well start or end in the middle of
+    /// a multi-byte character, so this always validates the shard's bytes.
+    ///
+    /// ```
+    /// # use vecshard::{ShardExt, VecShard};
+    /// let (hello, world) = Vec::from("hello wörld").split_inplace_at(6);
+    ///
+    /// assert_eq!(hello.as_str(), Ok("hello "));
+    /// assert_eq!(world.as_str(), Ok("wörld"));
+    /// ```
+    pub fn as_str(&self) -> Result<&str, Utf8Error> {
+        str::from_utf8(self)
+    }
+
+    /// View the bytes of this shard as a string slice, without checking that they're valid UTF-8.
+    ///
+    /// # Safety
+    ///
+    /// The bytes of this shard must be valid UTF-8, see [`str::from_utf8_unchecked`](std::str::from_utf8_unchecked).
+    pub unsafe fn as_str_unchecked(&self) -> &str {
+        str::from_utf8_unchecked(self)
+    }
+
+    /// View the bytes of this shard as a string, replacing anything that isn't valid UTF-8
+    /// with the replacement character `�`.
+    ///
+    /// This is [`String::from_utf8_lossy`], so it only allocates if there's something to replace.
+    ///
+    /// ```
+    /// # use vecshard::{ShardExt, VecShard};
+    /// // 'ö' is cut in half
+    /// let (hello, _) = Vec::from("hellö").split_inplace_at(5);
+    ///
+    /// assert_eq!(hello.to_string_lossy(), "hell\u{FFFD}");
+    /// ```
+    pub fn to_string_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(self)
+    }
+
+    /// Decode UTF-16 into a shard of UTF-8 bytes.
+    ///
+    /// Fails if `v` contains unpaired surrogates, just like [`String::from_utf16`].
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let utf16: Vec<u16> = "Grüß".encode_utf16().collect();
+    ///
+    /// assert_eq!(VecShard::from_utf16(&utf16).unwrap().as_str(), Ok("Grüß"));
+    /// assert!(VecShard::from_utf16(&[0xD800]).is_err());
+    /// ```
+    pub fn from_utf16(v: &[u16]) -> Result<VecShard<u8>, FromUtf16Error> {
+        String::from_utf16(v).map(VecShard::from)
+    }
+
+    /// Decode UTF-16 into a shard of UTF-8 bytes, replacing unpaired surrogates with `�`.
+    ///
+    /// This is [`String::from_utf16_lossy`].
+    pub fn from_utf16_lossy(v: &[u16]) -> VecShard<u8> {
+        VecShard::from(String::from_utf16_lossy(v))
+    }
+
+    /// Read everything from `reader` into a new shard.
+    ///
+    /// This is [`Read::read_to_end`](std::io::Read::read_to_end) into a fresh Vec, which grows
+    /// geometrically, so even large inputs only get copied a few times.
+    /// Afterwards, the bytes can be split up without copying them any further.
+    ///
+    /// ```
+    /// # use std::io::Cursor;
+    /// # use vecshard::{ShardExt, VecShard};
+    /// let shard = VecShard::from_reader(&mut Cursor::new("key=value")).unwrap();
+    /// let (key, value) = shard.split_inplace_at(3);
+    ///
+    /// assert_eq!(key.as_str(), Ok("key"));
+    /// assert_eq!(value.as_str(), Ok("=value"));
+    /// ```
+    pub fn from_reader<R: io::Read>(reader: &mut R) -> io::Result<VecShard<u8>> {
+        let mut vec = Vec::new();
+        reader.read_to_end(&mut vec)?;
+        Ok(VecShard::from(vec))
+    }
+
+    /// Try to turn this shard into a `String`.
+    ///
+    /// Like the conversion into a `Vec`, this re-uses the backing allocation if this is the only shard left
+    /// and copies the bytes otherwise.
+    ///
+    /// If the bytes aren't valid UTF-8, you get the shard back untouched in the error,
+    /// with [`ConversionFailure::InvalidUtf8`] as the reason.
+    pub fn try_into_string(self) -> Result<String, ConversionError<u8>> {
+        if let Err(err) = str::from_utf8(&self) {
+            return Err(ConversionError {
+                shard: self,
+                reason: ConversionFailure::InvalidUtf8(err),
+            });
+        }
+        let bytes: Vec<u8> = self.into();
+        Ok(unsafe { String::from_utf8_unchecked(bytes) })
+    }
+
+    /// Split a length-prefixed frame off the front of this shard.
+    ///
+    /// `read_len` gets the first `header_len` bytes and returns the length of the payload that follows them.
+    /// If the shard holds the whole frame, it is split into the frame (header included) and the rest in O(1).
+    ///
+    /// If the shard is too short for the header or the payload, you get it back untouched as the `Err`,
+    /// so you can wait for more bytes. That includes lengths so large that they overflow.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let buf = VecShard::from(vec![0, 2, b'h', b'i', 0, 9, b'?']);
+    /// let read_len = |header: &[u8]| u16::from_be_bytes([header[0], header[1]]) as usize;
+    ///
+    /// let (frame, rest) = buf.split_frame(2, read_len).unwrap();
+    /// assert_eq!(*frame, [0, 2, b'h', b'i']);
+    ///
+    /// // the next frame isn't complete yet
+    /// let rest = rest.split_frame(2, read_len).unwrap_err();
+    /// assert_eq!(*rest, [0, 9, b'?']);
+    /// ```
+    pub fn split_frame(
+        self,
+        header_len: usize,
+        read_len: impl FnOnce(&[u8]) -> usize,
+    ) -> Result<(VecShard<u8>, VecShard<u8>), VecShard<u8>> {
+        match self.frame_len(header_len, read_len) {
+            Some(frame_len) => Ok(self.split_inplace_at(frame_len)),
+            None => Err(self),
+        }
+    }
+
+    /// Split this shard into a stream of length-prefixed frames.
+    ///
+    /// This is like calling [`split_frame`](crate::VecShard::split_frame) over and over:
+    /// every complete frame at the front is split off in O(1) and returned as its own shard.
+    /// Once the rest of the bytes don't make up a whole frame, iteration stops and you can
+    /// get at them with [`remainder`](crate::iter::Frames::remainder).
+    ///
+    /// There's no way to tell a malformed length field apart from a frame that just hasn't fully
+    /// arrived yet, so a bogus length that's too large stops the iteration, leaving it and everything after it
+    /// in the remainder. Note that with `header_len == 0`, a `read_len` returning 0 yields empty frames forever.
+    ///
+    /// ```
+    /// # use vecshard::VecShard;
+    /// let buf = VecShard::from(vec![1, b'a', 2, b'b', b'c', 3, b'd']);
+    /// let mut frames = buf.frames(1, |header| header[0] as usize);
+    ///
+    /// assert_eq!(*frames.next().unwrap(), [1, b'a']);
+    /// assert_eq!(*frames.next().unwrap(), [2, b'b', b'c']);
+    /// assert!(frames.next().is_none());
+    /// assert_eq!(*frames.remainder(), [3, b'd']);
+    /// ```
+    pub fn frames<F: Fn(&[u8]) -> usize>(self, header_len: usize, read_len: F) -> Frames<F> {
+        Frames {
+            shard: self,
+            header_len,
+            read_len,
+        }
+    }
+
+    /// The length of the complete frame at the front of this shard, if there is one.
+    pub(crate) fn frame_len(
+        &self,
+        header_len: usize,
+        read_len: impl FnOnce(&[u8]) -> usize,
+    ) -> Option<usize> {
+        if self.len < header_len {
+            return None;
+        }
+        header_len
+            .checked_add(read_len(&self[..header_len]))
+            .filter(|&frame_len| frame_len <= self.len)
+    }
+}
+
+/// This re-uses the `String`'s buffer, so no bytes are copied.
+impl From<String> for VecShard<u8> {
+    fn from(s: String) -> Self {
+        VecShard::from(s.into_bytes())
+    }
+}
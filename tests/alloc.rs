@@ -0,0 +1,63 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use vecshard::VecShard;
+
+// Counts allocations per thread, so tests running in parallel don't get in each other's way
+struct Counting;
+
+thread_local! {
+    static ALLOCS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+fn allocations<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let before = ALLOCS.with(Cell::get);
+    let res = f();
+    (res, ALLOCS.with(Cell::get) - before)
+}
+
+#[test]
+fn empty_shards_dont_allocate() {
+    // the shared bookkeeping is allocated the first time it's needed
+    drop(VecShard::<u64>::new());
+    drop(VecShard::<()>::new());
+
+    let (shards, n) = allocations(|| {
+        let mut shard = VecShard::<u64>::new();
+        let taken = std::mem::take(&mut shard);
+        let default = VecShard::<u64>::default();
+        let from_vec = VecShard::from(Vec::<u64>::new());
+        (shard, taken, default, from_vec, VecShard::<()>::new())
+    });
+    assert_eq!(n, 0);
+    let (mut shard, taken, default, from_vec, zsts) = shards;
+
+    let (merged, n) = allocations(|| VecShard::merge(taken, default));
+    assert_eq!(n, 0);
+    assert_eq!(merged.len(), 0);
+    assert!(merged.is_whole());
+    assert!(format!("{:#?}", from_vec).contains("shard_count: 1,"));
+
+    let ((vec, reused), n) = allocations(|| from_vec.into_vec_reporting());
+    assert_eq!(n, 0);
+    assert_eq!(vec.capacity(), 0);
+    assert!(reused);
+
+    // growing one of them gets it an allocation of its own
+    shard.push(1);
+    assert_eq!(*shard, [1]);
+    assert_eq!(zsts.capacity(), 0);
+}
@@ -25,19 +25,473 @@ fn vec_roundtrip() {
     assert_eq!(vec, vec2);
 }
 
+#[test]
+fn from_wrapped_deque() {
+    use std::collections::VecDeque;
+
+    let mut deque = VecDeque::with_capacity(4);
+    deque.extend(&[1, 2, 3, 4]);
+    deque.pop_front();
+    deque.pop_front();
+    // these go into the freed slots at the front of the ring buffer
+    deque.push_back(5);
+    deque.push_back(6);
+    assert!(!deque.as_slices().1.is_empty());
+
+    let shard = VecShard::from(deque);
+    assert_eq!(*shard, [3, 4, 5, 6]);
+}
+
+#[test]
+fn from_boxed_slice() {
+    let boxed: Box<[_]> = vec![1, 2, 3].into_boxed_slice();
+    let ptr = boxed.as_ptr();
+
+    let shard = VecShard::from(boxed);
+    assert_eq!(shard.as_ptr(), ptr);
+    assert_eq!(*shard, [1, 2, 3]);
+    assert_eq!(shard.capacity(), 3);
+}
+
 #[test]
 fn into_vecs() {
     let (left, right) = vec![1, 11, 21, 12_11, 11_12_21, 31_22_11].split_inplace_at(3);
 
     // this one needs to allocate a new Vec, since right still exists
-    let lvec: Vec<_> = left.into();
+    let (lvec, reused) = left.into_vec_reporting();
+    assert!(!reused);
     // this one is now the only shard left and can re-use the allocation
-    let rvec: Vec<_> = right.into();
+    let (rvec, reused) = right.into_vec_reporting();
+    assert!(reused);
 
     assert_eq!(lvec, [1, 11, 21]);
     assert_eq!(rvec, [12_11, 11_12_21, 31_22_11]);
 }
 
+#[test]
+fn whole_shards() {
+    let mut vec = Vec::with_capacity(10);
+    vec.extend_from_slice(&["a", "b", "c"]);
+    let ptr = vec.as_ptr();
+
+    let shard = VecShard::from(vec);
+    assert!(shard.is_whole());
+    let vec: Vec<_> = shard.into();
+    assert_eq!(vec.as_ptr(), ptr);
+    assert_eq!(vec.capacity(), 10);
+
+    let (left, right) = vec.split_inplace_at(2);
+    assert!(!left.is_whole());
+    assert!(!right.is_whole());
+    // even with its sibling gone, left is missing an element
+    drop(right);
+    assert!(!left.is_whole());
+
+    let mut shard = VecShard::from(vec![1, 2]);
+    shard.next();
+    assert!(!shard.is_whole());
+    assert!(VecShard::<u8>::new().is_whole());
+}
+
+#[test]
+fn into_vec_after_sibling_dropped() {
+    let vec = vec!["links", "rechts", "geradeaus"];
+    let old_ptr = vec.as_ptr();
+
+    let (left, right) = vec.split_inplace_at(1);
+    std::mem::drop(left);
+
+    // right is the only shard left, so it can move to the front and re-use the allocation
+    let rvec: Vec<_> = right.into();
+    assert_eq!(rvec, ["rechts", "geradeaus"]);
+    assert_eq!(rvec.as_ptr(), old_ptr);
+
+    let (left, right) = vec!["oben", "unten"].split_inplace_at(1);
+    let old_ptr = left.as_ptr();
+    std::mem::drop(right);
+
+    let lvec: Vec<_> = left.into();
+    assert_eq!(lvec, ["oben"]);
+    assert_eq!(lvec.as_ptr(), old_ptr);
+}
+
+#[test]
+fn into_vec_iter_reuses_allocation() {
+    let shard = VecShard::from(vec![1u32, 2, 3]);
+    let old_ptr = shard.as_ptr();
+
+    let doubled: Vec<_> = shard.into_vec_iter().map(|x| x * 2).collect();
+    assert_eq!(doubled, [2, 4, 6]);
+    assert_eq!(doubled.as_ptr(), old_ptr);
+}
+
+#[test]
+fn into_vec_iter_on_shared_shard() {
+    let (left, right) = vec![1u32, 2, 3, 4].split_inplace_at(2);
+    let old_ptr = left.as_ptr();
+
+    // right still needs the allocation, so left has to be copied out first
+    let doubled: Vec<_> = left.into_vec_iter().map(|x| x * 2).collect();
+    assert_eq!(doubled, [2, 4]);
+    assert_ne!(doubled.as_ptr(), old_ptr);
+    assert_eq!(*right, [3, 4]);
+}
+
+#[test]
+fn conversion_stats() {
+    use vecshard::ConversionStats;
+
+    let (vec, stats) = VecShard::from(vec![1u16, 2, 3]).into_vec_stats();
+    assert_eq!(vec, [1, 2, 3]);
+    assert_eq!(
+        stats,
+        ConversionStats {
+            reused_allocation: true,
+            bytes_copied: 0,
+            shard_count: 1,
+        }
+    );
+
+    // the left half doesn't need to move either, once it's alone
+    let (left, right) = vec![1u16, 2, 3].split_inplace_at(2);
+    drop(right);
+    assert_eq!(left.into_vec_stats().1.bytes_copied, 0);
+
+    let (left, right) = vec![1u16, 2, 3].split_inplace_at(2);
+    drop(left);
+    let (vec, stats) = right.into_vec_stats();
+    assert_eq!(vec, [3]);
+    assert!(stats.reused_allocation);
+    assert_eq!(stats.bytes_copied, 2);
+
+    let (left, right) = vec![1u16, 2, 3].split_inplace_at(1);
+    let stats = left.into_vec_stats().1;
+    assert!(!stats.reused_allocation);
+    assert_eq!(stats.bytes_copied, 2);
+    assert_eq!(stats.shard_count, 2);
+    drop(right);
+}
+
+#[test]
+fn array_conversion() {
+    use std::convert::TryFrom;
+    use vecshard::error::ConversionFailure;
+
+    let words: Vec<String> = "a b c".split(' ').map(String::from).collect();
+    let (left, right) = words.split_inplace_at(2);
+
+    let err = <[String; 2]>::try_from(right).unwrap_err();
+    assert_eq!(
+        err.reason,
+        ConversionFailure::WrongLength {
+            expected: 2,
+            actual: 1
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "Can't convert shard because the shard has 1 elements instead of 2."
+    );
+    assert_eq!(*err.shard, ["c"]);
+
+    let array = <[String; 2]>::try_from(left).unwrap();
+    assert_eq!(array, ["a", "b"]);
+    // the right half still owns its element
+    drop(err);
+
+    let empty = <[u8; 0]>::try_from(VecShard::new()).unwrap();
+    assert_eq!(empty, []);
+}
+
+#[test]
+fn pooled_buffers() {
+    use std::mem::MaybeUninit;
+
+    struct Pool {
+        free: Vec<Box<[MaybeUninit<String>]>>,
+    }
+
+    impl Pool {
+        fn fill(&mut self, words: &[&str]) -> VecShard<String> {
+            let mut buf = self.free.pop().unwrap();
+            for (slot, word) in buf.iter_mut().zip(words) {
+                slot.write(word.to_string());
+            }
+            unsafe { VecShard::from_uninit(buf, words.len()) }
+        }
+
+        fn reclaim(&mut self, shard: VecShard<String>) -> Vec<String> {
+            let len = shard.len();
+            let buf = shard.into_uninit();
+            let taken = buf[..len].iter().map(|s| unsafe { s.assume_init_read() }).collect();
+            self.free.push(buf);
+            taken
+        }
+    }
+
+    let mut pool = Pool {
+        free: vec![Box::new_uninit_slice(4)],
+    };
+    let storage = pool.free[0].as_ptr();
+
+    let shard = pool.fill(&["a", "b", "c"]);
+    assert_eq!(shard.capacity(), 4);
+    let (left, right) = shard.split_inplace_at(1);
+    drop(left);
+
+    // the right half is alone, so it gets moved to the front of the same storage
+    assert_eq!(pool.reclaim(right), ["b", "c"]);
+    assert_eq!(pool.free[0].as_ptr(), storage);
+    assert_eq!(pool.free[0].len(), 4);
+
+    let shard = pool.fill(&["d", "e"]);
+    let (left, right) = shard.split_inplace_at(1);
+    // with its sibling still around, the left half has to be copied into a buffer of its own
+    assert_eq!(pool.reclaim(left), ["d"]);
+    assert_eq!(pool.free[0].len(), 1);
+    assert_eq!(*right, ["e"]);
+    drop(right);
+
+    let zsts = VecShard::from(vec![(); 3]).into_uninit();
+    assert_eq!(zsts.len(), 3);
+}
+
+#[test]
+fn appending_vecs() {
+    use std::{cell::RefCell, rc::Rc};
+
+    #[derive(Debug, PartialEq)]
+    struct Loud(u32, Rc<RefCell<Vec<u32>>>);
+
+    impl Drop for Loud {
+        fn drop(&mut self) {
+            self.1.borrow_mut().push(self.0);
+        }
+    }
+
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let loud = |i| Loud(i, log.clone());
+
+    let (mut left, right) = vec![loud(1), loud(2), loud(3)].split_inplace_at(1);
+    left.append_vec(vec![loud(4), loud(5)]);
+    assert_eq!(left.iter().map(|l| l.0).collect::<Vec<_>>(), [1, 4, 5]);
+    // the right half wasn't touched
+    assert_eq!(right.iter().map(|l| l.0).collect::<Vec<_>>(), [2, 3]);
+    assert_eq!(*log.borrow(), []);
+
+    let mut empty = VecShard::new();
+    let vec = vec![loud(6)];
+    let ptr = vec.as_ptr();
+    empty.append_vec(vec);
+    // an empty shard just takes over the Vec
+    assert_eq!(empty.as_ptr(), ptr);
+    empty.append_vec(Vec::new());
+    assert_eq!(empty.len(), 1);
+
+    drop(left);
+    drop(right);
+    drop(empty);
+    // every element was dropped exactly once
+    let mut dropped = log.borrow().clone();
+    dropped.sort_unstable();
+    assert_eq!(dropped, [1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn reverse_draining() {
+    let (left, right) = vec![1, 2, 3, 4, 5].split_inplace_at(4);
+    let mut drain = left.drain_rev();
+    assert_eq!(drain.len(), 4);
+    assert_eq!(drain.next(), Some(4));
+    assert_eq!(drain.next_back(), Some(1));
+    assert_eq!(drain.collect::<Vec<_>>(), [3, 2]);
+    assert_eq!(*right, [5]);
+}
+
+#[test]
+fn reverse_draining_dropped_early() {
+    use std::rc::Rc;
+
+    let counter = Rc::new(());
+    let mut drain = VecShard::from(vec![counter.clone(); 4]).drain_rev();
+    assert_eq!(Rc::strong_count(&counter), 5);
+
+    drop(drain.next());
+    assert_eq!(drain.len(), 3);
+    assert_eq!(Rc::strong_count(&counter), 4);
+
+    // the rest goes down with the iterator
+    drop(drain);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+fn pinned_memory() {
+    let mut vec = Vec::with_capacity(16);
+    vec.extend(0u16..8);
+    let (left, rest) = vec.split_inplace_at(2);
+    let (middle, right) = rest.split_inplace_at(3);
+    drop(middle);
+
+    // two shards, one allocation
+    let shards = vec![left, right];
+    assert_eq!(VecShard::pinned_bytes(&shards), 32);
+
+    let mut shards = shards;
+    shards.push(VecShard::from(vec![1u16; 5]));
+    assert_eq!(VecShard::pinned_bytes(&shards), 42);
+
+    assert_eq!(VecShard::<u16>::pinned_bytes(&[]), 0);
+    assert_eq!(VecShard::pinned_bytes(&[VecShard::from(vec![(); 10])]), 0);
+}
+
+#[test]
+fn extracting_like_vec() {
+    let not_triple = |x: &u32| !x.is_multiple_of(3);
+    for len in 0..10 {
+        let mut expected: Vec<u32> = (0..len).collect();
+        let extracted_expected: Vec<u32> = expected.iter().copied().filter(not_triple).collect();
+        expected.retain(|x| !not_triple(x));
+
+        let (mut left, right) = (0..len + 2).collect::<Vec<_>>().split_inplace_at(len as usize);
+        let extracted: Vec<u32> = left.extract_if(|x| not_triple(x)).collect();
+        assert_eq!(extracted, extracted_expected);
+        assert_eq!(*left, *expected);
+        // every extracted element became spare capacity
+        assert_eq!(left.capacity(), len as usize);
+        assert_eq!(*right, [len, len + 1]);
+
+        left.push(100);
+        assert_eq!(left[expected.len()], 100);
+    }
+}
+
+#[test]
+fn extracting_stopped_early() {
+    use std::rc::Rc;
+
+    let counter = Rc::new(());
+    let mut shard = VecShard::from(vec![counter.clone(); 6]);
+    let mut seen = 0u32;
+    let mut extract = shard.extract_if(|_| {
+        seen += 1;
+        seen.is_multiple_of(2)
+    });
+    drop(extract.next());
+    drop(extract.next());
+    drop(extract);
+
+    // the two extracted elements are gone, the rest are kept, including the ones never looked at
+    assert_eq!(shard.len(), 4);
+    assert_eq!(Rc::strong_count(&counter), 5);
+    drop(shard);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+fn extracting_with_panicking_filter() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let words: Vec<String> = "a b c d".split(' ').map(String::from).collect();
+    let mut shard = VecShard::from(words);
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        shard
+            .extract_if(|s| match s.as_str() {
+                "a" => true,
+                "c" => panic!("oh no"),
+                _ => false,
+            })
+            .for_each(drop)
+    }));
+    assert!(res.is_err());
+    // the element the filter panicked on is kept along with the rest
+    assert_eq!(*shard, ["b", "c", "d"]);
+}
+
+#[test]
+fn newtype_casts() {
+    #[repr(transparent)]
+    #[derive(Debug, PartialEq)]
+    struct Name(String);
+
+    let words: Vec<String> = "ab cd ef".split(' ').map(String::from).collect();
+    let ptr = words.as_ptr();
+    let (left, right) = words.split_inplace_at(2);
+
+    let mut names: VecShard<Name> = unsafe { left.cast() };
+    assert_eq!(names.as_ptr() as *const String, ptr);
+    assert_eq!(names[1], Name(String::from("cd")));
+    names[0].0.push('!');
+
+    let left: VecShard<String> = unsafe { names.cast() };
+    assert_eq!(*left, ["ab!", "cd"]);
+    // casting doesn't get in the way of merging back together
+    let whole = VecShard::merge_inplace(left, right).unwrap();
+    assert_eq!(*whole, ["ab!", "cd", "ef"]);
+    assert_eq!(whole.as_ptr(), ptr);
+}
+
+#[test]
+fn shards_over_a_shared_buffer() {
+    use std::rc::Rc;
+
+    let counter = Rc::new(());
+    let mut owner = VecShard::prepare_buffer(5);
+    let base = owner.as_ptr();
+    for slot in owner.spare_capacity_mut() {
+        slot.write(counter.clone());
+    }
+
+    let front = unsafe { VecShard::from_shared_parts(&owner, 0..2) };
+    let back = unsafe { VecShard::from_shared_parts(&owner, 2..5) };
+    drop(owner);
+    assert_eq!(front.as_ptr(), base);
+    assert_eq!(front.capacity(), 2);
+    assert_eq!(back.capacity(), 3);
+
+    // they're siblings like any others
+    let whole = VecShard::merge_inplace(front, back).unwrap();
+    assert_eq!(whole.len(), 5);
+    assert_eq!(Rc::strong_count(&counter), 6);
+
+    let (front, back) = whole.split_inplace_at(2);
+    assert_eq!(front.count(), 2);
+    assert_eq!(Rc::strong_count(&counter), 4);
+    assert_eq!(back.count(), 3);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+fn keeping_position() {
+    use vecshard::error::ConversionFailure;
+
+    let words: Vec<String> = "a b c d".split(' ').map(String::from).collect();
+    let base = words.as_ptr();
+
+    let (left, right) = words.split_inplace_at(2);
+    // the sibling is still around
+    let err = right.into_vec_keep_position().unwrap_err();
+    assert_eq!(err.reason, ConversionFailure::WouldAllocate);
+    assert_eq!(
+        err.to_string(),
+        "Can't convert shard because the shard isn't the only one left in its allocation, so it would have to be copied."
+    );
+    let right = err.shard;
+    drop(left);
+
+    let (mut buf, range) = right.into_vec_keep_position().unwrap();
+    assert_eq!(range, 2..4);
+    assert_eq!(buf.len(), 4);
+    assert_eq!(buf.as_ptr() as *const String, base);
+
+    // the elements are still there and still ours to drop
+    let taken: Vec<String> = range.map(|i| unsafe { buf[i].assume_init_read() }).collect();
+    assert_eq!(taken, ["c", "d"]);
+    buf[0].write(String::from("z"));
+    assert_eq!(unsafe { buf[0].assume_init_ref() }, "z");
+    unsafe { buf[0].assume_init_drop() };
+}
+
 #[test]
 fn things_get_dropped() {
     use std::rc::Rc;
@@ -62,6 +516,179 @@ fn things_get_dropped() {
     Rc::try_unwrap(rc).unwrap();
 }
 
+#[test]
+fn unchecked_split() {
+    let shard = VecShard::from(vec![2, 3, 5, 7, 11]);
+    let (left, right) = unsafe { shard.split_inplace_at_unchecked(2) };
+
+    assert_eq!(*left, [2, 3]);
+    assert_eq!(*right, [5, 7, 11]);
+}
+
+#[test]
+fn predicate_splits() {
+    let shard = VecShard::from(vec!["0", "1", "x", "2", "x"]);
+
+    let (left, right) = shard.clone().split_at_predicate(|s| *s == "0");
+    assert_eq!(left.len(), 0);
+    assert_eq!(*right, ["0", "1", "x", "2", "x"]);
+
+    let (left, right) = shard.clone().split_at_predicate(|s| *s == "x");
+    assert_eq!(*left, ["0", "1"]);
+    assert_eq!(*right, ["x", "2", "x"]);
+
+    let (left, right) = shard.split_at_predicate(|s| *s == "y");
+    assert_eq!(*left, ["0", "1", "x", "2", "x"]);
+    assert_eq!(right.len(), 0);
+}
+
+#[test]
+fn mutable_chunks() {
+    let (mut left, right) = (0..10u32).collect::<Vec<_>>().split_inplace_at(7);
+    let mut sizes = Vec::new();
+    for chunk in left.chunks_mut_owned(3) {
+        sizes.push(chunk.len());
+        chunk.iter_mut().for_each(|x| *x += 100);
+    }
+    assert_eq!(sizes, [3, 3, 1]);
+    assert_eq!(*left, [100, 101, 102, 103, 104, 105, 106]);
+    // the other shard is left alone
+    assert_eq!(*right, [7, 8, 9]);
+}
+
+#[test]
+fn disjoint_splits() {
+    let (mut left, mut right) = VecShard::from(vec![1, 2, 3, 4]).split_disjoint(1);
+    left[0] = 10;
+    right.iter_mut().for_each(|x| *x *= 2);
+    assert_eq!(*left, [10]);
+    assert_eq!(*right, [4, 6, 8]);
+}
+
+#[test]
+fn disjoint_subshards() {
+    use std::rc::Rc;
+
+    let items: Vec<Rc<u32>> = (0..8).map(Rc::new).collect();
+    let weak: Vec<_> = items.iter().map(Rc::downgrade).collect();
+    let values = |shard: &VecShard<Rc<u32>>| shard.iter().map(|rc| **rc).collect::<Vec<_>>();
+
+    let shard = VecShard::from(items);
+    // overlapping, out of bounds and reversed ranges are all rejected
+    let shard = shard.try_subshards(&[0..3, 5..7, 2..4]).unwrap_err();
+    let shard = shard.try_subshards(&[0..3, 6..9]).unwrap_err();
+    #[allow(clippy::reversed_empty_ranges)]
+    let shard = shard.try_subshards(&[0..1, 3..1]).unwrap_err();
+    assert_eq!(shard.len(), 8);
+
+    let pieces = shard.try_subshards(&[5..7, 0..2, 2..2, 3..4]).unwrap();
+    assert_eq!(values(&pieces[0]), [5, 6]);
+    assert_eq!(values(&pieces[1]), [0, 1]);
+    assert_eq!(pieces[2].len(), 0);
+    assert_eq!(values(&pieces[3]), [3]);
+
+    // the elements that weren't in any range are gone, the others are still around
+    let alive: Vec<bool> = weak.iter().map(|w| w.upgrade().is_some()).collect();
+    assert_eq!(alive, [true, true, false, true, false, true, true, false]);
+
+    drop(pieces);
+    assert!(weak.iter().all(|w| w.upgrade().is_none()));
+}
+
+#[test]
+fn halving() {
+    let (left, right) = VecShard::from(vec![1, 2, 3, 4]).split_in_half();
+    assert_eq!(*left, [1, 2]);
+    assert_eq!(*right, [3, 4]);
+
+    let (left, right) = VecShard::from(vec![1, 2, 3]).split_in_half();
+    assert_eq!(*left, [1, 2]);
+    assert_eq!(*right, [3]);
+
+    let (left, right) = VecShard::from(vec![1]).split_in_half();
+    assert_eq!(*left, [1]);
+    assert_eq!(right.len(), 0);
+
+    let (left, right) = VecShard::<u8>::new().split_in_half();
+    assert_eq!(left.len(), 0);
+    assert_eq!(right.len(), 0);
+}
+
+#[test]
+fn tagged_split() {
+    use vecshard::Side;
+
+    let (left, right, side) = VecShard::from(vec![1, 2, 3, 4, 5]).split_inplace_at_tagged(2);
+    assert_eq!(side, Side::Left);
+    assert_eq!(*left, [1, 2]);
+    assert_eq!(*right, [3, 4, 5]);
+
+    let (_, _, side) = right.split_inplace_at_tagged(1);
+    assert_eq!(side, Side::Neither);
+
+    let (empty, left, side) = left.split_inplace_at_tagged(0);
+    assert_eq!(side, Side::Right);
+    assert_eq!(empty.len(), 0);
+
+    // the shard tagged as starting at the front converts without moving, once it's unique
+    let ptr = left.as_ptr();
+    std::mem::drop(empty);
+    let vec: Vec<_> = left.into();
+    assert_eq!(vec.as_ptr(), ptr);
+}
+
+#[test]
+fn batched_drops() {
+    use std::rc::Rc;
+
+    let rc = Rc::new(());
+    let shard = VecShard::from(vec![rc.clone(); 10]);
+
+    let mut batches = shard.drop_in_batches(3);
+    assert_eq!(batches.len(), 4);
+
+    assert_eq!(batches.next(), Some(3));
+    assert_eq!(Rc::strong_count(&rc), 8);
+    assert_eq!(batches.next(), Some(3));
+    assert_eq!(Rc::strong_count(&rc), 5);
+
+    // dropping the iterator early drops the rest
+    std::mem::drop(batches);
+    Rc::try_unwrap(rc).unwrap();
+}
+
+#[test]
+fn auto_shrinking() {
+    let shard = VecShard::from((0..100).collect::<Vec<_>>());
+    let ptr = shard.as_ptr();
+
+    let mut drain = shard.auto_shrink(0.5);
+    for i in 0..50 {
+        assert_eq!(drain.next(), Some(i));
+    }
+    // exactly half is left, so nothing happened yet
+    assert_eq!(unsafe { drain.as_ptr().offset_from(ptr) }, 50);
+
+    assert_eq!(drain.next_back(), Some(99));
+    // now we're below the threshold, so the rest moved
+    let shard = drain.into_inner();
+    assert_eq!(shard.capacity(), 49);
+    assert_eq!(*shard, (50..99).collect::<Vec<_>>()[..]);
+
+    // even with a high threshold, each shrink shrinks by a fraction, so there are only a few of them
+    let mut drain = VecShard::from(vec![0u32; 1000]).auto_shrink(0.9);
+    let mut ptr = drain.as_ptr();
+    let mut moves = 0;
+    while drain.next().is_some() {
+        // without a move, the front just advances by one
+        if drain.as_ptr() != ptr.wrapping_add(1) {
+            moves += 1;
+        }
+        ptr = drain.as_ptr();
+    }
+    assert!(moves < 100);
+}
+
 #[test]
 fn clone_works() {
     let vec = vec![1, 2, 6, 24, 120];
@@ -71,6 +698,23 @@ fn clone_works() {
     assert_eq!(*left, [1, 2, 6]);
 }
 
+#[test]
+fn multiset_equality() {
+    let shard = VecShard::from(vec![1, 2, 2, 3, 3, 3]);
+
+    assert!(shard.eq_multiset(&shard));
+    assert!(shard.eq_multiset(&VecShard::from(vec![3, 2, 3, 1, 3, 2])));
+    // same elements, different counts
+    assert!(!shard.eq_multiset(&VecShard::from(vec![1, 1, 2, 3, 3, 3])));
+    // different lengths
+    assert!(!shard.eq_multiset(&VecShard::from(vec![1, 2, 3])));
+    assert!(!shard.eq_multiset(&VecShard::from(vec![1, 2, 2, 3, 3, 3, 3])));
+
+    let (left, right) = vec![5, 6, 7].split_inplace_at(1);
+    let swapped = VecShard::merge_swapped(right, left);
+    assert!(swapped.eq_multiset(&VecShard::from(vec![7, 6, 5])));
+}
+
 #[test]
 fn hash_works() {
     use std::collections::hash_map::DefaultHasher;
@@ -89,6 +733,30 @@ fn hash_works() {
     assert_eq!(get_hash(&right), get_hash(&VecShard::from(vec![8, 7])));
 }
 
+#[test]
+fn chunked_hashing() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let (_, shard) = (0..1000u32).map(|i| i.to_string()).collect::<Vec<_>>().split_inplace_at(10);
+    let mut whole = DefaultHasher::new();
+    shard.hash(&mut whole);
+    let whole = whole.finish();
+
+    for &chunk in &[1, 7, 990, 5000] {
+        let mut chunked = DefaultHasher::new();
+        shard.hash_chunks(&mut chunked, chunk);
+        assert_eq!(chunked.finish(), whole);
+    }
+
+    let bytes = VecShard::from(vec![42u8; 4096]);
+    let mut whole = DefaultHasher::new();
+    bytes.hash(&mut whole);
+    let mut chunked = DefaultHasher::new();
+    bytes.hash_chunks(&mut chunked, 100);
+    assert_eq!(chunked.finish(), whole.finish());
+}
+
 #[test]
 fn debug_looks_ok() {
     use std::fmt::Write;
@@ -100,6 +768,36 @@ fn debug_looks_ok() {
     assert_eq!(buf, "[1, 3, 1, 2]");
 }
 
+#[test]
+fn display_with_separator() {
+    use vecshard::shard;
+
+    let shard = shard![1, 2, 3];
+    assert_eq!(shard.display(", ").to_string(), "1, 2, 3");
+    assert_eq!(shard.display("").to_string(), "123");
+    assert_eq!(format!("{:>3}", shard.display(",")), "  1,  2,  3");
+
+    // only the shard's own elements show up
+    let (left, right) = vec!["a", "b", "c"].split_inplace_at(1);
+    assert_eq!(right.display(" -> ").to_string(), "b -> c");
+    assert_eq!(left.display(" -> ").to_string(), "a");
+    assert_eq!(VecShard::<u8>::new().display(", ").to_string(), "");
+}
+
+#[test]
+fn verbose_debug() {
+    let (_left, right) = vec![1, 3, 1, 2].split_inplace_at(1);
+
+    let verbose = format!("{:#?}", right);
+    assert!(verbose.contains("offset: 1,"));
+    assert!(verbose.contains("len: 3,"));
+    assert!(verbose.contains("backing_capacity: 4,"));
+    assert!(verbose.contains("shard_count: 2,"));
+
+    // the regular form stays the same
+    assert_eq!(format!("{:?}", right), "[3, 1, 2]");
+}
+
 #[test]
 fn lucky_merges() {
     let dish = vec!["mashed potatoes", "liquor", "pie", "jellied eels"];
@@ -120,6 +818,319 @@ fn lucky_merges() {
     assert_eq!(old_ptr, new_ptr);
 }
 
+#[test]
+fn offset_shards_reuse_their_allocation() {
+    let mut vec = Vec::with_capacity(10);
+    vec.extend(["a", "b", "c", "d", "e", "f"].iter().map(|s| s.to_string()));
+    let ptr = vec.as_ptr();
+
+    let (left, right) = vec.split_inplace_at(2);
+    drop(left);
+    // the right shard is all alone, but doesn't start at the front of the allocation
+    assert_ne!(right.as_ptr(), ptr);
+
+    let (vec, stats) = right.into_vec_stats();
+    assert_eq!(vec, ["c", "d", "e", "f"]);
+    assert!(stats.reused_allocation);
+    assert_eq!(stats.shard_count, 1);
+    // the elements are moved to the front, into the very same allocation
+    assert_eq!(vec.as_ptr(), ptr);
+    assert_eq!(vec.capacity(), 10);
+
+    // same thing through the plain conversion
+    let (left, right) = vec.split_inplace_at(3);
+    drop(left);
+    let vec = Vec::from(right);
+    assert_eq!(vec, ["f"]);
+    assert_eq!((vec.as_ptr(), vec.capacity()), (ptr, 10));
+}
+
+#[test]
+fn splitting_capacity() {
+    let mut vec = Vec::with_capacity(11);
+    vec.extend(["a", "b", "c", "d"].iter().map(|s| s.to_string()));
+    let ptr = vec.as_ptr();
+
+    let (mut left, mut right) = VecShard::from(vec).split_with_capacity(1);
+    // 7 spare elements, the right half gets the bigger share
+    assert_eq!(left.capacity(), 4);
+    assert_eq!(right.capacity(), 7);
+    assert_eq!(left.as_ptr(), ptr);
+    assert_eq!(right.as_ptr(), ptr.wrapping_add(4));
+
+    // both halves can grow without reallocating
+    for s in &["x", "y", "z"] {
+        left.push(s.to_string());
+        right.push(s.to_string());
+    }
+    assert_eq!(*left, ["a", "x", "y", "z"]);
+    assert_eq!(*right, ["b", "c", "d", "x", "y", "z"]);
+    assert_eq!(left.as_ptr(), ptr);
+    assert_eq!(right.as_ptr(), ptr.wrapping_add(4));
+
+    // and merge back into the same allocation
+    let merged = VecShard::merge(left, right);
+    assert_eq!(merged.as_ptr(), ptr);
+    assert_eq!(*merged, ["a", "x", "y", "z", "b", "c", "d", "x", "y", "z"]);
+
+    // with a sibling around, nothing can move, so it's a plain split
+    let mut vec = Vec::with_capacity(10);
+    vec.extend_from_slice(&[1, 2, 3, 4]);
+    let (sibling, rest) = vec.split_inplace_at(1);
+    let (left, right) = rest.split_with_capacity(1);
+    assert_eq!((left.capacity(), right.capacity()), (1, 8));
+    drop(sibling);
+
+    // and the same goes for shards without any spare capacity
+    let (left, right) = VecShard::from(vec![1, 2, 3]).split_with_capacity(1);
+    assert_eq!((left.capacity(), right.capacity()), (1, 2));
+}
+
+#[test]
+fn joining_into_vecs() {
+    fn words(n: usize) -> Vec<String> {
+        let mut vec = Vec::with_capacity(n + 2);
+        vec.extend((0..n).map(|i| i.to_string()));
+        vec
+    }
+
+    // only two shards left, so every layout ends up at the front of the same allocation
+    #[allow(clippy::type_complexity)]
+    let layouts: &[fn(Vec<String>) -> (VecShard<String>, VecShard<String>)] = &[
+        // adjacent and in order
+        |vec| vec.split_inplace_at(3),
+        // in order, with gaps in front and in between
+        |vec| {
+            let (_, rest) = vec.split_inplace_at(1);
+            let (left, rest) = rest.split_inplace_at(2);
+            (left, rest.split_inplace_at(2).1)
+        },
+        // reversed, but right isn't in the way of left's final place
+        |vec| {
+            let (_, rest) = vec.split_inplace_at(3);
+            let (right, rest) = rest.split_inplace_at(1);
+            (rest.split_inplace_at(1).1, right)
+        },
+        // reversed, with room for right to go first
+        |vec| {
+            let (right, rest) = vec.split_inplace_at(1);
+            (rest.split_inplace_at(3).1, right)
+        },
+        // reversed and too tight for either
+        |vec| {
+            let (right, left) = vec.split_inplace_at(2);
+            (left, right)
+        },
+    ];
+
+    for layout in layouts {
+        let vec = words(6);
+        let ptr = vec.as_ptr();
+        let (left, right) = layout(vec);
+        let expected: Vec<String> = left.iter().chain(right.iter()).cloned().collect();
+
+        let vec = VecShard::join_into_vec(left, right);
+        assert_eq!(vec, expected);
+        assert_eq!((vec.as_ptr(), vec.capacity()), (ptr, 8));
+    }
+
+    // with a third shard in the way, the elements have to be copied out
+    let vec = words(6);
+    let ptr = vec.as_ptr();
+    let (left, rest) = vec.split_inplace_at(2);
+    let (middle, right) = rest.split_inplace_at(2);
+    let vec = VecShard::join_into_vec(left, right);
+    assert_eq!(vec, ["0", "1", "4", "5"]);
+    assert_ne!(vec.as_ptr(), ptr);
+    assert_eq!(*middle, ["2", "3"]);
+
+    // and the same goes for shards from different allocations
+    let vec = VecShard::join_into_vec(VecShard::from(words(2)), middle);
+    assert_eq!(vec, ["0", "1", "2", "3"]);
+}
+
+#[test]
+fn grouping_runs() {
+    let vec = vec![1, 1, 2, 2, 2, 3];
+    let ptr = vec.as_ptr();
+
+    let runs = VecShard::from(vec).group_runs(|a, b| a == b);
+    assert_eq!(runs.len(), 3);
+    assert_eq!(*runs[0], [1, 1]);
+    assert_eq!(*runs[1], [2, 2, 2]);
+    assert_eq!(*runs[2], [3]);
+    // all in the same allocation
+    assert_eq!(runs[0].as_ptr(), ptr);
+    assert_eq!(runs[2].as_ptr(), ptr.wrapping_add(5));
+
+    // runs of ascending elements
+    let runs = VecShard::from(vec![1, 2, 3, 2, 5, 1]).group_runs(|a, b| a < b);
+    let lens: Vec<_> = runs.iter().map(|run| run.len()).collect();
+    assert_eq!(lens, [3, 2, 1]);
+
+    // one big run, and no runs at all
+    assert_eq!(VecShard::from(vec![7; 4]).group_runs(|a, b| a == b).len(), 1);
+    assert_eq!(VecShard::<u8>::new().group_runs(|a, b| a == b).len(), 0);
+}
+
+#[test]
+fn predicted_merge_costs() {
+    use vecshard::MergeCost::{self, *};
+
+    // checks the prediction against what actually happens
+    fn merge_and_check(left: VecShard<u32>, right: VecShard<u32>, expected: MergeCost) -> VecShard<u32> {
+        assert_eq!(VecShard::merge_cost(&left, &right), expected);
+        let err = match VecShard::merge_inplace(left, right) {
+            Ok(merged) => {
+                assert_eq!(expected, InPlace);
+                return merged;
+            }
+            Err(err) => err,
+        };
+        match VecShard::merge_noalloc(err.left, err.right) {
+            Ok(merged) => {
+                assert_eq!(expected, Move);
+                merged
+            }
+            Err(err) => {
+                assert_eq!(expected, Allocate);
+                VecShard::merge(err.left, err.right)
+            }
+        }
+    }
+
+    let (a, rest) = (0..6).collect::<Vec<u32>>().split_inplace_at(2);
+    let (b, c) = rest.split_inplace_at(2);
+    // b is in the way
+    let ac = merge_and_check(a, c, Allocate);
+    // b is alone now, but ac lives in a new allocation
+    let acb = merge_and_check(ac, b, Allocate);
+    // empty shards merge with anything
+    let acb = merge_and_check(acb, VecShard::new(), InPlace);
+    let (front, back) = acb.split_inplace_at(1);
+    // the wrong way around, but adjacent
+    let rotated = merge_and_check(back, front, Move);
+    assert_eq!(*rotated, [1, 4, 5, 2, 3, 0]);
+
+    let (a, rest) = (0..8).collect::<Vec<u32>>().split_inplace_at(2);
+    let (b, rest) = rest.split_inplace_at(2);
+    let (c, d) = rest.split_inplace_at(2);
+    let ab = merge_and_check(a, b, InPlace);
+    drop(c);
+    // with c gone, d can be moved over
+    let abd = merge_and_check(ab, d, Move);
+    assert_eq!(*abd, [0, 1, 2, 3, 6, 7]);
+}
+
+#[test]
+fn merge_policies() {
+    use std::rc::Rc;
+    use vecshard::MergePolicy;
+
+    let limit = MergePolicy { max_move_bytes: 16 };
+    assert_eq!(MergePolicy::default().max_move_bytes, usize::MAX);
+
+    // 16 bytes to rotate, that's still fine
+    let vec = vec![1u32, 2, 3, 4];
+    let base = vec.as_ptr();
+    let (left, right) = vec.split_inplace_at(1);
+    let merged = VecShard::merge_with_policy(right, left, limit);
+    assert_eq!(*merged, [2, 3, 4, 1]);
+    assert_eq!(merged.as_ptr(), base);
+
+    // 20 bytes is too many
+    let vec = vec![1u32, 2, 3, 4, 5];
+    let base = vec.as_ptr();
+    let (left, right) = vec.split_inplace_at(2);
+    let merged = VecShard::merge_with_policy(right, left, limit);
+    assert_eq!(*merged, [3, 4, 5, 1, 2]);
+    assert_ne!(merged.as_ptr(), base);
+    assert!(merged.is_whole());
+
+    // in-place merges don't move anything, no matter how big
+    let vec = vec![1u32, 2, 3, 4, 5];
+    let base = vec.as_ptr();
+    let (left, right) = vec.split_inplace_at(2);
+    let merged = VecShard::merge_with_policy(left, right, limit);
+    assert_eq!(merged.as_ptr(), base);
+
+    // and neither does the default policy
+    let vec: Vec<_> = (0..1000u32).collect();
+    let base = vec.as_ptr();
+    let (left, right) = vec.split_inplace_at(300);
+    let merged = VecShard::merge_with_policy(right, left, MergePolicy::default());
+    assert_eq!(merged.as_ptr(), base);
+    assert_eq!(merged[700], 0);
+
+    // each element is dropped exactly once either way
+    let (left, right) = vec![Rc::new(1), Rc::new(2), Rc::new(3)].split_inplace_at(1);
+    let tracked = Rc::clone(&left[0]);
+    let merged = VecShard::merge_with_policy(right, left, MergePolicy { max_move_bytes: 0 });
+    assert_eq!(Rc::strong_count(&tracked), 2);
+    drop(merged);
+    assert_eq!(Rc::strong_count(&tracked), 1);
+}
+
+#[test]
+fn light_merges() {
+    use vecshard::error::WouldMove::*;
+
+    let (a, rest) = vec![1, 2, 3, 4].split_inplace_at(1);
+    let (b, c) = rest.split_inplace_at(2);
+
+    let (a, c, reason) = VecShard::try_merge_inplace_light(a, c).unwrap_err();
+    assert_eq!(reason, NotAdjacent);
+    assert_eq!(*a, [1]);
+    assert_eq!(*c, [4]);
+    let (c, b, reason) = VecShard::try_merge_inplace_light(c, b).unwrap_err();
+    assert_eq!(reason, WrongOrder);
+    let (a, _, reason) = VecShard::try_merge_inplace_light(a, VecShard::from(vec![5])).unwrap_err();
+    assert_eq!(reason, DifferentAllocations);
+
+    let ab = VecShard::try_merge_inplace_light(a, b).unwrap();
+    let abc = VecShard::try_merge_inplace_light(ab, c).unwrap();
+    assert_eq!(*abc, [1, 2, 3, 4]);
+}
+
+#[test]
+fn three_way_merges() {
+    let vec: Vec<u32> = (0..8).collect();
+    let base = vec.as_ptr();
+    let (b, rest) = vec.split_inplace_at(2);
+    let (c, rest) = rest.split_inplace_at(2);
+    let (gap, a) = rest.split_inplace_at(2);
+    drop(gap);
+
+    // b and c go first, which makes it possible to move a over without allocating
+    let merged = VecShard::merge3(a, b, c);
+    assert_eq!(*merged, [6, 7, 0, 1, 2, 3]);
+    assert_eq!(merged.as_ptr(), base);
+
+    // the same, starting with a and b
+    let vec: Vec<u32> = (0..8).collect();
+    let base = vec.as_ptr();
+    let (c, rest) = vec.split_inplace_at(2);
+    let (gap, rest) = rest.split_inplace_at(2);
+    let (a, b) = rest.split_inplace_at(2);
+    drop(gap);
+    let merged = VecShard::merge3(a, b, c);
+    assert_eq!(*merged, [4, 5, 6, 7, 0, 1]);
+    // a and b are moved left to make room for c, all within the old allocation
+    assert_eq!(merged.as_ptr(), base.wrapping_add(2));
+
+    // three separate allocations are copied into a single new one that fits exactly
+    let merged = VecShard::merge3(
+        VecShard::from(vec![1]),
+        VecShard::from(vec![2, 3]),
+        VecShard::from(vec![4]),
+    );
+    assert_eq!(*merged, [1, 2, 3, 4]);
+    assert_eq!(merged.capacity(), 4);
+
+    let merged = VecShard::merge3(VecShard::new(), VecShard::from(vec![1]), VecShard::new());
+    assert_eq!(*merged, [1]);
+}
+
 #[test]
 fn unlucky_merges() {
     use vecshard::error::{CantMerge, WouldMove::*};
@@ -178,6 +1189,82 @@ fn unlucky_merges() {
     );
 }
 
+#[test]
+fn joined_slices() {
+    let (left, rest) = vec![1, 2, 3, 4, 5].split_inplace_at(2);
+    let (middle, right) = rest.split_inplace_at(1);
+
+    assert_eq!(VecShard::joined_slice(&left, &middle), Some(&[1, 2, 3][..]));
+    assert_eq!(VecShard::joined_slice(&middle, &right), Some(&[3, 4, 5][..]));
+    // not adjacent
+    assert_eq!(VecShard::joined_slice(&left, &right), None);
+    // wrong order
+    assert_eq!(VecShard::joined_slice(&middle, &left), None);
+    // different allocations
+    let other = VecShard::from(vec![6]);
+    assert_eq!(VecShard::joined_slice(&right, &other), None);
+}
+
+#[test]
+fn overlapping_shards() {
+    let (left, rest) = vec![1, 2, 3, 4, 5].split_inplace_at(2);
+    let (middle, right) = rest.split_inplace_at(1);
+
+    // a shard fully overlaps itself
+    assert!(VecShard::overlaps(&middle, &middle));
+    // adjacent
+    assert!(!VecShard::overlaps(&left, &middle));
+    assert!(!VecShard::overlaps(&middle, &left));
+    // disjoint
+    assert!(!VecShard::overlaps(&left, &right));
+    // different allocations
+    assert!(!VecShard::overlaps(&left, &VecShard::from(vec![1, 2])));
+
+    let (empty, _) = right.split_inplace_at(0);
+    assert!(!VecShard::overlaps(&empty, &empty));
+
+    let (a, b) = vec![(); 4].split_inplace_at(2);
+    assert!(VecShard::overlaps(&a, &a));
+    assert!(!VecShard::overlaps(&a, &b));
+}
+
+#[test]
+fn mergeable_grouping() {
+    let (a0, rest) = vec![1, 2, 3, 4].split_inplace_at(1);
+    let (a1, rest) = rest.split_inplace_at(1);
+    let (a2, a3) = rest.split_inplace_at(1);
+    let (b0, rest) = vec![5, 6, 7].split_inplace_at(1);
+    let (b1, b2) = rest.split_inplace_at(1);
+    let (empty, a0) = a0.split_inplace_at(0);
+
+    let shards = vec![a0, a1, b0, b1, empty, b2, a2, a3];
+    let groups = VecShard::mergeable_groups(&shards);
+    assert_eq!(groups, [0..2, 2..6, 6..8]);
+
+    // the groups really do merge in place
+    let mut shards = shards.into_iter();
+    let merged: Vec<Vec<i32>> = groups
+        .iter()
+        .map(|g| {
+            let first = shards.next().unwrap();
+            shards
+                .by_ref()
+                .take(g.len() - 1)
+                .fold(first, |acc, s| VecShard::merge_inplace(acc, s).unwrap())
+                .into()
+        })
+        .collect();
+    assert_eq!(merged, [vec![1, 2], vec![5, 6, 7], vec![3, 4]]);
+
+    // an empty shard doesn't glue together shards that aren't adjacent
+    let (left, rest) = vec![1, 2, 3].split_inplace_at(1);
+    let (_middle, right) = rest.split_inplace_at(1);
+    let shards = [left, VecShard::new(), right];
+    assert_eq!(VecShard::mergeable_groups(&shards), [0..2, 2..3]);
+
+    assert_eq!(VecShard::<u8>::mergeable_groups(&[]), []);
+}
+
 #[test]
 fn weird_merges() {
     let vec = vec![1, 4, 9, 16, 25, 36, 49, 64];
@@ -229,6 +1316,897 @@ fn weird_merges() {
     assert_eq!(*outer, [25, 36, 49, 64, 1, 4]);
 }
 
+#[test]
+fn gapped_reverse_merges() {
+    let words: Vec<String> = "a b c d e f g h".split(' ').map(String::from).collect();
+
+    // (end of the front shard, start of the back shard)
+    for &(front_end, back_start) in &[(2, 5), (3, 4), (1, 6), (4, 5), (3, 7), (1, 2)] {
+        let (front, rest) = words.clone().split_inplace_at(front_end);
+        let (gap, back) = rest.split_inplace_at(back_start - front_end);
+        std::mem::drop(gap);
+        let start = front.as_ptr();
+
+        // the back shard goes first, so everything has to be shuffled around
+        let merged = VecShard::merge(back, front);
+
+        let mut expected = words[back_start..].to_vec();
+        expected.extend_from_slice(&words[..front_end]);
+        assert_eq!(*merged, *expected);
+
+        // no matter how it was done, it didn't need a new allocation
+        let offset = (merged.as_ptr() as usize - start as usize) / std::mem::size_of::<String>();
+        assert!(offset + merged.capacity() <= words.len());
+    }
+}
+
+#[test]
+fn merged_drop_order() {
+    use std::{cell::RefCell, rc::Rc};
+
+    struct Loud(usize, Rc<RefCell<Vec<usize>>>);
+    impl Drop for Loud {
+        fn drop(&mut self) {
+            self.1.borrow_mut().push(self.0);
+        }
+    }
+
+    // Each pattern describes the original Vec of 8 elements as in the merge_patterns bench:
+    // 'l' and 'r' go into the two shards, 'x' stays in a guard shard, '-' gets dropped up front.
+    let patterns = [
+        "llllrrrr", "llrr----", "--llrr--", "ll----rr", "rrrrllll", "rr--llll", "rrrr--ll",
+        "r------l", "rrr-l---", "-r-llll-", "rrr-ll--", "rr-lll--", "llxxxxrr", "rrxxxxll",
+        "lxr-----",
+    ];
+
+    for pattern in &patterns {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let items: Vec<_> = (0..8).map(|i| Loud(i, log.clone())).collect();
+
+        // cut the Vec into runs of the same letter
+        let mut runs = Vec::new();
+        let mut rest = VecShard::from(items);
+        let bytes = pattern.as_bytes();
+        let mut start = 0;
+        for end in 1..=bytes.len() {
+            if end == bytes.len() || bytes[end] != bytes[start] {
+                let (run, tail) = rest.split_inplace_at(end - start);
+                runs.push((bytes[start], run));
+                rest = tail;
+                start = end;
+            }
+        }
+
+        let (mut left, mut right, mut guard) = (Vec::new(), Vec::new(), Vec::new());
+        for (kind, run) in runs {
+            match kind {
+                b'l' => left.push(run),
+                b'r' => right.push(run),
+                b'x' => guard.push(run),
+                _ => std::mem::drop(run),
+            }
+        }
+        let left = left.into_iter().fold(VecShard::new(), VecShard::merge);
+        let right = right.into_iter().fold(VecShard::new(), VecShard::merge);
+        let expected: Vec<_> = left.iter().chain(right.iter()).map(|x| x.0).collect();
+
+        log.borrow_mut().clear();
+        std::mem::drop(VecShard::merge(left, right));
+        assert_eq!(*log.borrow(), expected, "{}", pattern);
+
+        // everything else gets dropped exactly once too
+        std::mem::drop(guard);
+        let mut all = log.borrow().clone();
+        all.sort();
+        let kept: Vec<_> = (0..8).filter(|&i| bytes[i] != b'-').collect();
+        assert_eq!(all, kept, "{}", pattern);
+    }
+
+    // shards from different allocations
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let left = VecShard::from(vec![Loud(0, log.clone()), Loud(1, log.clone())]);
+    let right = VecShard::from(vec![Loud(2, log.clone())]);
+    std::mem::drop(VecShard::merge(left, right));
+    assert_eq!(*log.borrow(), [0, 1, 2]);
+}
+
+#[test]
+fn directed_merges() {
+    use vecshard::Direction::{self, *};
+
+    fn offset_after(pattern: &str, dir: Direction) -> (Vec<u32>, usize, usize) {
+        let vec: Vec<u32> = (0..8).collect();
+        let base = vec.as_ptr();
+
+        let (front, rest) = vec.split_inplace_at(2);
+        let (middle, back) = rest.split_inplace_at(3);
+        let merged = match pattern {
+            "ll---rrr" => {
+                drop(middle);
+                VecShard::merge_toward(front, back, dir)
+            }
+            "rr---lll" => {
+                drop(middle);
+                VecShard::merge_toward(back, front, dir)
+            }
+            "rrlll---" => {
+                drop(back);
+                VecShard::merge_toward(middle, front, dir)
+            }
+            _ => unreachable!(),
+        };
+        let offset = (merged.as_ptr() as usize - base as usize) / 4;
+        (merged.to_vec(), offset, merged.capacity())
+    }
+
+    assert_eq!(offset_after("ll---rrr", Low), (vec![0, 1, 5, 6, 7], 0, 8));
+    assert_eq!(offset_after("ll---rrr", High), (vec![0, 1, 5, 6, 7], 3, 5));
+    assert_eq!(offset_after("rr---lll", Low), (vec![5, 6, 7, 0, 1], 0, 8));
+    assert_eq!(offset_after("rr---lll", High), (vec![5, 6, 7, 0, 1], 3, 5));
+    assert_eq!(offset_after("rrlll---", Low), (vec![2, 3, 4, 0, 1], 0, 8));
+    assert_eq!(offset_after("rrlll---", High), (vec![2, 3, 4, 0, 1], 3, 5));
+
+    // nothing has to move, so nothing does
+    let (left, right) = vec![1, 2, 3, 4].split_inplace_at(1);
+    let (right, guard) = right.split_inplace_at(1);
+    let ptr = left.as_ptr();
+    let merged = VecShard::merge_toward(left, right, High);
+    assert_eq!(merged.as_ptr(), ptr);
+
+    // other shards in the way, so it's rotated right where it is
+    let (merged, rest) = merged.split_inplace_at(1);
+    let merged = VecShard::merge_toward(rest, merged, High);
+    assert_eq!(*merged, [2, 1]);
+    assert_eq!(merged.as_ptr(), ptr);
+    assert_eq!(*guard, [3, 4]);
+}
+
+#[test]
+fn merge_with_capacity_hint() {
+    let (left, rest) = vec![1, 2, 3, 4, 5, 6].split_inplace_at(2);
+    let (_guard, right) = rest.split_inplace_at(2);
+
+    // the guard is still around, so this has to allocate
+    let merged = VecShard::merge_with_capacity(left, right, 10);
+    assert_eq!(*merged, [1, 2, 5, 6]);
+
+    // merged is unique, so converting it keeps the hinted allocation
+    let vec: Vec<_> = merged.into();
+    assert!(vec.capacity() >= 14);
+}
+
+#[test]
+fn swapped_merges() {
+    let (left, right) = vec![1, 2, 3, 4, 5].split_inplace_at(2);
+
+    // reverse order, so we get the elements as they lie in memory
+    let all = VecShard::merge_swapped(right, left);
+    assert_eq!(*all, [1, 2, 3, 4, 5]);
+
+    // still keeps the order when it can
+    let (left, right) = vec![1, 2, 3, 4, 5].split_inplace_at(2);
+    let all = VecShard::merge_swapped(left, right);
+    assert_eq!(*all, [1, 2, 3, 4, 5]);
+
+    let different = VecShard::from(vec![6, 7]);
+    let all = VecShard::merge_swapped(all, different);
+    assert_eq!(*all, [1, 2, 3, 4, 5, 6, 7]);
+}
+
+#[test]
+fn remerge_keeps_allocation() {
+    let mut shard = VecShard::from((0..32).collect::<Vec<_>>());
+    let old_ptr = shard.as_ptr();
+
+    for at in 0..=32 {
+        let (left, right) = shard.split_inplace_at(at);
+
+        // alternate between the in-order and reverse cases
+        shard = if at % 2 == 0 {
+            VecShard::merge(left, right)
+        } else {
+            VecShard::merge(right, left)
+        };
+        assert_eq!(shard.as_ptr(), old_ptr);
+    }
+
+    // punch holes into the shard, the rest still only has two shards so it stays put
+    while shard.len() > 1 {
+        let (left, rest) = shard.split_inplace_at(1);
+        let (hole, right) = rest.split_inplace_at(1);
+        std::mem::drop(hole);
+
+        shard = VecShard::merge(left, right);
+        assert_eq!(shard.as_ptr(), old_ptr);
+    }
+}
+
+#[test]
+fn joins() {
+    let reference = [1, 2, 0, 3, 0, 4, 5, 6];
+
+    let parts = vec![
+        VecShard::from(vec![1, 2]),
+        VecShard::from(vec![3]),
+        VecShard::from(vec![4, 5, 6]),
+    ];
+    assert_eq!(*VecShard::join_owned(parts, 0), reference);
+
+    // one of the parts is unique and big enough, so the result should end up in there
+    let mut big = Vec::with_capacity(20);
+    big.push(3);
+    let big_ptr = big.as_ptr();
+    let parts = vec![
+        VecShard::from(vec![1, 2]),
+        VecShard::from(big),
+        VecShard::from(vec![4, 5, 6]),
+    ];
+    let joined = VecShard::join_owned(parts, 0);
+    assert_eq!(*joined, reference);
+    assert_eq!(joined.as_ptr(), big_ptr);
+
+    // ..but not if there's a sibling still around
+    let mut big = Vec::with_capacity(20);
+    big.extend_from_slice(&[3, 7]);
+    let big_ptr = big.as_ptr();
+    let (big, _sibling) = big.split_inplace_at(1);
+    let parts = vec![VecShard::from(vec![1, 2]), big, VecShard::from(vec![4, 5, 6])];
+    let joined = VecShard::join_owned(parts, 0);
+    assert_eq!(*joined, reference);
+    assert!(joined.as_ptr() != big_ptr);
+
+    assert_eq!(VecShard::join_owned(Vec::new(), 0).len(), 0);
+    assert_eq!(*VecShard::join_owned(vec![VecShard::from(vec![1])], 0), [1]);
+}
+
+#[test]
+fn flattening() {
+    // adjacent chunks go back together without moving
+    let shard = VecShard::from(vec![1, 2, 3, 4, 5, 6, 7]);
+    let ptr = shard.as_ptr();
+    let flat = VecShard::flatten_shards(shard.into_shard_chunks(3));
+    assert_eq!(*flat, [1, 2, 3, 4, 5, 6, 7]);
+    assert_eq!(flat.as_ptr(), ptr);
+
+    // interleaved with shards from elsewhere
+    let (a, rest) = vec![1, 2, 3, 4].split_inplace_at(1);
+    let (b, c) = rest.split_inplace_at(2);
+    let parts = vec![
+        a,
+        b,
+        VecShard::from(vec![10, 11]),
+        VecShard::new(),
+        c,
+        VecShard::from(vec![12]),
+    ];
+    assert_eq!(*VecShard::flatten_shards(parts), [1, 2, 3, 10, 11, 4, 12]);
+
+    let nothing = VecShard::<u8>::flatten_shards(Vec::new());
+    assert_eq!(nothing.len(), 0);
+}
+
+#[test]
+fn fallible_allocations() {
+    let (left, rest) = vec![1, 2, 3, 4, 5].split_inplace_at(2);
+    let (middle, right) = rest.split_inplace_at(1);
+
+    // middle is still around, so this needs to allocate
+    let outer = VecShard::try_merge(left, right).unwrap();
+    assert_eq!(*outer, [1, 2, 4, 5]);
+
+    // this one doesn't
+    let (l, r) = outer.split_inplace_at(2);
+    let outer = VecShard::try_merge(r, l).unwrap();
+    assert_eq!(*outer, [4, 5, 1, 2]);
+
+    let (mid, _rest) = middle.split_inplace_at(1);
+    assert_eq!(mid.try_into_vec().unwrap(), [3]);
+    assert_eq!(outer.try_into_vec().unwrap(), [4, 5, 1, 2]);
+}
+
+#[test]
+fn sorted_merges() {
+    // interleaved
+    let (left, right) = vec![1, 3, 5, 7, 2, 4, 6].split_inplace_at(4);
+    assert_eq!(*VecShard::merge_sorted(left, right), [1, 2, 3, 4, 5, 6, 7]);
+
+    // disjoint, in both orders
+    let (left, right) = vec![1, 2, 3, 4, 5, 6].split_inplace_at(3);
+    assert_eq!(*VecShard::merge_sorted(left, right), [1, 2, 3, 4, 5, 6]);
+    let (left, right) = vec![4, 5, 6, 1, 2, 3].split_inplace_at(3);
+    assert_eq!(*VecShard::merge_sorted(left, right), [1, 2, 3, 4, 5, 6]);
+
+    // from different allocations
+    let merged = VecShard::merge_sorted(
+        VecShard::from(vec![0, 10, 20, 30]),
+        VecShard::from(vec![5, 15]),
+    );
+    assert_eq!(*merged, [0, 5, 10, 15, 20, 30]);
+
+    // equal elements from the left come first
+    let left = VecShard::from(vec![(1, 'l'), (2, 'l'), (2, 'l'), (3, 'l')]);
+    let right = VecShard::from(vec![(2, 'r'), (3, 'r')]);
+    let merged = VecShard::merge_sorted(left, right);
+    let keys: Vec<_> = merged.iter().map(|&(_, side)| side).collect();
+    assert_eq!(keys, ['l', 'l', 'l', 'r', 'l', 'r']);
+
+    // compare with a plain sort on something bigger
+    let mut left: Vec<u32> = (0..200).map(|i| (i * 7919) % 1000).collect();
+    let mut right: Vec<u32> = (0..150).map(|i| (i * 104_729) % 1000).collect();
+    left.sort();
+    right.sort();
+    let mut reference = [&left[..], &right[..]].concat();
+    reference.sort();
+    let merged = VecShard::merge_sorted(VecShard::from(left), VecShard::from(right));
+    assert_eq!(*merged, reference[..]);
+}
+
+#[test]
+fn lazy_chunks() {
+    let vec: Vec<_> = (0..23).map(|i| i.to_string()).collect();
+    let reference: Vec<Vec<String>> = vec.chunks(5).map(|c| c.to_vec()).collect();
+
+    let chunks = VecShard::from(vec).into_shard_chunks(5);
+    assert_eq!(chunks.len(), 5);
+    let chunks: Vec<Vec<String>> = chunks.map(Vec::from).collect();
+    assert_eq!(chunks, reference);
+
+    let mut chunks = VecShard::from(vec![1, 2, 3, 4]).into_shard_chunks(2);
+    assert_eq!(chunks.len(), 2);
+    chunks.next();
+    assert_eq!(chunks.len(), 1);
+    chunks.next();
+    assert!(chunks.next().is_none());
+
+    assert_eq!(VecShard::from(Vec::<u8>::new()).into_shard_chunks(3).count(), 0);
+}
+
+#[test]
+fn drain_extrema() {
+    use std::{cmp::Ordering, rc::Rc};
+
+    // only compares the first field, so we can tell equal elements apart
+    #[derive(Debug)]
+    struct Counted {
+        key: u8,
+        tag: char,
+        _rc: Rc<()>,
+    }
+    impl PartialEq for Counted {
+        fn eq(&self, other: &Self) -> bool {
+            self.key == other.key
+        }
+    }
+    impl Eq for Counted {}
+    impl PartialOrd for Counted {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Counted {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.key.cmp(&other.key)
+        }
+    }
+    let counter = Rc::new(());
+    let make = |key, tag| Counted {
+        key,
+        tag,
+        _rc: counter.clone(),
+    };
+
+    let shard = VecShard::from(vec![make(2, 'a'), make(1, 'b'), make(3, 'c'), make(1, 'd')]);
+    let min = shard.drain_min().unwrap();
+    assert_eq!((min.key, min.tag), (1, 'b'));
+    // everything else has been dropped
+    assert_eq!(Rc::strong_count(&counter), 2);
+    drop(min);
+
+    let shard = VecShard::from(vec![make(3, 'a'), make(1, 'b'), make(3, 'c'), make(2, 'd')]);
+    let (_, rest) = shard.split_inplace_at(0);
+    let max = rest.drain_max().unwrap();
+    assert_eq!((max.key, max.tag), (3, 'c'));
+    assert_eq!(Rc::strong_count(&counter), 2);
+    drop(max);
+    assert_eq!(Rc::strong_count(&counter), 1);
+
+    assert_eq!(VecShard::from(Vec::<u8>::new()).drain_min(), None);
+    assert_eq!(VecShard::from(Vec::<u8>::new()).drain_max(), None);
+    assert_eq!(VecShard::from(vec![7]).drain_max(), Some(7));
+}
+
+#[test]
+fn split_into_n() {
+    let vec: Vec<_> = (0..10).collect();
+    let ptr = vec.as_ptr();
+
+    let shards = vec.split_inplace_into(4);
+    let lens: Vec<_> = shards.iter().map(|s| s.len()).collect();
+    assert_eq!(lens, [3, 3, 2, 2]);
+    assert_eq!(shards[0].as_ptr(), ptr);
+    assert_eq!(*shards[3], [8, 9]);
+
+    let shard = VecShard::from(vec!['a', 'b', 'c']);
+    let shards = shard.split_inplace_into(5);
+    let lens: Vec<_> = shards.iter().map(|s| s.len()).collect();
+    assert_eq!(lens, [1, 1, 1, 0, 0]);
+
+    let (_, right) = vec![1, 2, 3, 4, 5].split_inplace_at(1);
+    let shards = right.split_inplace_into(1);
+    assert_eq!(shards.len(), 1);
+    assert_eq!(*shards[0], [2, 3, 4, 5]);
+}
+
+#[test]
+#[should_panic]
+fn split_into_zero() {
+    vec![1, 2, 3].split_inplace_into(0);
+}
+
+#[test]
+fn ordered_merges() {
+    // adjacent
+    let (a, b) = vec![1, 2, 3, 4].split_inplace_at(2);
+    assert_eq!(*VecShard::merge_ordered(a, b), [1, 2, 3, 4]);
+
+    // reverse-adjacent
+    let (a, b) = vec![1, 2, 3, 4].split_inplace_at(1);
+    assert_eq!(*VecShard::merge_ordered(b, a), [2, 3, 4, 1]);
+
+    // not adjacent, the only two shards left
+    let (a, rest) = vec![1, 2, 3, 4, 5].split_inplace_at(1);
+    let (gap, b) = rest.split_inplace_at(2);
+    drop(gap);
+    assert_eq!(*VecShard::merge_ordered(b, a), [4, 5, 1]);
+
+    // not adjacent, with another shard in between
+    let (a, rest) = vec![1, 2, 3, 4, 5].split_inplace_at(2);
+    let (gap, b) = rest.split_inplace_at(1);
+    assert_eq!(*VecShard::merge_ordered(b, a), [4, 5, 1, 2]);
+    assert_eq!(*gap, [3]);
+
+    // different allocations
+    let merged = VecShard::merge_ordered(VecShard::from(vec![9]), VecShard::from(vec![7, 8]));
+    assert_eq!(*merged, [9, 7, 8]);
+}
+
+#[test]
+fn sorted_dedup() {
+    let words = vec!["ant", "ant", "bee", "cat", "cat", "cat", "dog", "dog"];
+    let shard = VecShard::from_sorted_dedup(words.into_iter().map(String::from));
+    assert_eq!(*shard, ["ant", "bee", "cat", "dog"]);
+    assert_eq!(shard.capacity(), 4);
+
+    let shard = VecShard::from_sorted_dedup(std::iter::repeat_n(4, 100));
+    assert_eq!(*shard, [4]);
+
+    let shard = VecShard::from_sorted_dedup(Vec::<u8>::new());
+    assert_eq!(shard.len(), 0);
+}
+
+#[test]
+fn repeated_elements() {
+    let shard = VecShard::from_elem(String::from("hi"), 4);
+    assert_eq!(*shard, ["hi", "hi", "hi", "hi"]);
+    assert_eq!(shard.capacity(), 4);
+
+    let zeroes = VecShard::from_elem(0u64, 1000);
+    assert!(zeroes.iter().all(|&x| x == 0));
+    assert_eq!(zeroes.len(), 1000);
+
+    let none = VecShard::from_elem(String::from("unused"), 0);
+    assert_eq!(none.len(), 0);
+}
+
+#[test]
+fn shard_macro() {
+    use vecshard::shard;
+
+    let list = shard!["a", "b", "c"];
+    assert_eq!(*list, ["a", "b", "c"]);
+    assert_eq!(list.capacity(), 3);
+
+    let trailing = shard![1, 2,];
+    assert_eq!(*trailing, [1, 2]);
+
+    let repeated = shard![String::from("x"); 3];
+    assert_eq!(*repeated, ["x", "x", "x"]);
+
+    let empty: VecShard<u8> = shard![];
+    assert_eq!(empty.len(), 0);
+    assert_eq!(empty.capacity(), 0);
+}
+
+#[test]
+fn split_where_callback() {
+    let shard = VecShard::from(vec![1, 2, 3, 0, 4, 5]);
+    let (left, right) = shard.split_where(|s| s.iter().position(|&x| x == 0).unwrap());
+    assert_eq!(*left, [1, 2, 3]);
+    assert_eq!(*right, [0, 4, 5]);
+
+    let (all, none) = right.split_where(|s| s.len());
+    assert_eq!(*all, [0, 4, 5]);
+    assert_eq!(none.len(), 0);
+}
+
+#[test]
+#[should_panic]
+fn split_where_out_of_bounds() {
+    VecShard::from(vec![1, 2, 3]).split_where(|s| s.len() + 1);
+}
+
+#[test]
+fn uninit_writes() {
+    let mut vec = Vec::with_capacity(8);
+    vec.extend(["a", "b", "c"].iter().map(|s| s.to_string()));
+    let (mut left, mut right) = vec.split_inplace_at(1);
+
+    // the left shard doesn't own any spare room
+    assert_eq!(left.spare_capacity_mut().len(), 0);
+
+    let spare = right.spare_capacity_mut();
+    assert_eq!(spare.len(), 5);
+    spare[0].write(String::from("d"));
+    spare[1].write(String::from("e"));
+    unsafe { right.set_len(4) };
+    assert_eq!(*right, ["b", "c", "d", "e"]);
+    assert_eq!(right.capacity(), 7);
+    assert_eq!(right.spare_capacity_mut().len(), 3);
+
+    // the left shard has to reallocate first
+    left.reserve(1);
+    left.spare_capacity_mut()[0].write(String::from("z"));
+    unsafe { left.set_len(2) };
+    assert_eq!(*left, ["a", "z"]);
+}
+
+#[test]
+#[cfg(not(feature = "wasm-single-thread"))]
+fn fill_then_split() {
+    use std::thread;
+
+    let mut buf = VecShard::prepare_buffer(10);
+    assert_eq!(buf.len(), 0);
+    assert!(buf.capacity() >= 10);
+    let base = buf.as_ptr();
+
+    // produce
+    for (i, slot) in buf.spare_capacity_mut()[..10].iter_mut().enumerate() {
+        slot.write(i as u64);
+    }
+    unsafe { buf.set_len(10) };
+
+    // hand the pieces to the consumers
+    let pieces = buf.split_inplace_into(4);
+    assert_eq!(pieces[0].as_ptr(), base);
+    let sums: Vec<u64> = pieces
+        .into_iter()
+        .map(|piece| thread::spawn(move || piece.sum::<u64>()))
+        .map(|handle| handle.join().unwrap())
+        .collect();
+    assert_eq!(sums, [3, 12, 13, 17]);
+}
+
+#[test]
+fn empty_shards() {
+    let empty: VecShard<String> = VecShard::new();
+    assert_eq!(empty.len(), 0);
+    assert_eq!(empty.capacity(), 0);
+    assert_eq!(VecShard::<u8>::default().len(), 0);
+
+    // merging with an empty shard just hands back the other one
+    let (left, right) = vec![1, 2, 3, 4].split_inplace_at(2);
+    let ptr = right.as_ptr();
+    let merged = VecShard::merge(VecShard::new(), right);
+    assert_eq!(merged.as_ptr(), ptr);
+    assert_eq!(*merged, [3, 4]);
+
+    let ptr = left.as_ptr();
+    let merged = VecShard::merge(left, VecShard::new());
+    assert_eq!(merged.as_ptr(), ptr);
+    assert_eq!(*merged, [1, 2]);
+}
+
+#[test]
+fn merging_empties() {
+    let full = || VecShard::from(vec![1, 2, 3]);
+
+    // empty left
+    let right = full();
+    let ptr = right.as_ptr();
+    let merged = VecShard::merge_inplace(VecShard::new(), right).unwrap();
+    assert_eq!((merged.as_ptr(), &*merged), (ptr, &[1, 2, 3][..]));
+
+    // empty right
+    let left = full();
+    let ptr = left.as_ptr();
+    let merged = VecShard::merge_noalloc(left, VecShard::new()).unwrap();
+    assert_eq!((merged.as_ptr(), &*merged), (ptr, &[1, 2, 3][..]));
+
+    // both empty
+    let merged = VecShard::merge_inplace(VecShard::<u8>::new(), VecShard::new()).unwrap();
+    assert_eq!(merged.len(), 0);
+
+    // an empty shard from the same allocation, but not adjacent
+    let (a, rest) = vec![1, 2, 3].split_inplace_at(1);
+    let (_b, c) = rest.split_inplace_at(1);
+    let (_, empty) = a.split_inplace_at(1);
+    let ptr = c.as_ptr();
+    let merged = VecShard::merge_with_capacity(empty, c, 10);
+    assert_eq!((merged.as_ptr(), &*merged), (ptr, &[3][..]));
+
+    // fold-style accumulation never copies the first shard
+    let first = full();
+    let ptr = first.as_ptr();
+    let acc = vec![first]
+        .into_iter()
+        .fold(VecShard::new(), VecShard::merge);
+    assert_eq!(acc.as_ptr(), ptr);
+}
+
+#[test]
+fn into_vec_with_min_capacity() {
+    // unique and big enough: re-used as is
+    let mut vec = Vec::with_capacity(16);
+    vec.extend_from_slice(&[1, 2, 3]);
+    let ptr = vec.as_ptr();
+    let vec = VecShard::from(vec).into_vec_with_capacity(10);
+    assert_eq!((vec.as_ptr(), vec.capacity()), (ptr, 16));
+
+    // unique, but too small: grown
+    let (_, right) = vec.split_inplace_at(1);
+    let vec = right.into_vec_with_capacity(40);
+    assert_eq!(vec, [2, 3]);
+    assert!(vec.capacity() >= 40);
+
+    // shared: copied into a new Vec
+    let (left, right) = vec![1, 2, 3, 4].split_inplace_at(3);
+    let vec = left.into_vec_with_capacity(5);
+    assert_eq!(vec, [1, 2, 3]);
+    assert!(vec.capacity() >= 5);
+    assert!(vec.as_ptr() != right.as_ptr().wrapping_sub(3));
+
+    // the requested capacity is smaller than the length
+    let vec = VecShard::from(vec![7; 8]).into_vec_with_capacity(2);
+    assert_eq!(vec.len(), 8);
+}
+
+#[test]
+fn inplace_maps() {
+    use std::rc::Rc;
+
+    #[derive(Debug, PartialEq)]
+    struct Wrapped(u64);
+
+    // same layout, unique
+    let shard = VecShard::from(vec![1u64, 2, 3]);
+    let ptr = shard.as_ptr() as *const Wrapped;
+    let wrapped = shard.map_inplace(Wrapped);
+    assert_eq!(wrapped.as_ptr(), ptr);
+    assert_eq!(*wrapped, [Wrapped(1), Wrapped(2), Wrapped(3)]);
+
+    // same layout, but shared
+    let (left, right) = vec![1u64, 2, 3].split_inplace_at(1);
+    let mapped = right.map_inplace(|x| x as i64 * 10);
+    assert_eq!(*mapped, [20, 30]);
+    assert_eq!(*left, [1]);
+
+    // different layout
+    let shard = VecShard::from(vec![1u8, 2, 3]);
+    assert_eq!(*shard.map_inplace(u32::from), [1, 2, 3]);
+
+    // zero-sized
+    let shard = VecShard::from(vec![(); 4]);
+    assert_eq!(shard.map_inplace(|()| [0u8; 0]).len(), 4);
+
+    // a panic halfway through drops everything exactly once
+    let rc = Rc::new(());
+    let shard = VecShard::from(vec![rc.clone(); 6]);
+    let mut calls = 0;
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        shard.map_inplace(|x| {
+            calls += 1;
+            if calls == 4 {
+                panic!("oh no");
+            }
+            Some(x)
+        })
+    }));
+    assert!(res.is_err());
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+#[cfg(not(feature = "wasm-single-thread"))]
+fn shared_shards() {
+    use std::thread;
+    use vecshard::SharedShard;
+
+    let (left, right) = vec![1u64, 2, 3, 4, 5, 6].split_inplace_at(3);
+    let shared = SharedShard::from(right);
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let shared = shared.clone();
+            thread::spawn(move || shared.iter().sum::<u64>())
+        })
+        .collect();
+    for h in handles {
+        assert_eq!(h.join().unwrap(), 15);
+    }
+
+    assert_eq!(*left, [1, 2, 3]);
+    let other = shared.clone();
+    let shared = shared.try_unwrap().unwrap_err();
+    drop(other);
+    assert_eq!(*shared.try_unwrap().unwrap(), [4, 5, 6]);
+
+    let arc = VecShard::from(vec!['a']).into_shared();
+    assert_eq!(*SharedShard::from(arc), ['a']);
+}
+
+#[test]
+fn empty_vec_edge_cases() {
+    // Vec::new doesn't allocate, so these shards all point to a dangling pointer
+    let (left, right) = Vec::<String>::new().split_inplace_at(0);
+    assert_eq!((left.len(), right.len()), (0, 0));
+
+    let (a, b) = right.split_inplace_at(0);
+    let merged = VecShard::merge_inplace(a, b).unwrap();
+    let merged = VecShard::merge_noalloc(left, merged).unwrap();
+    assert_eq!(merged.len(), 0);
+    assert!(format!("{:#?}", merged).contains("offset: 0"));
+
+    let (left, right) = Vec::<u32>::new().split_inplace_at(0);
+    let merged = VecShard::merge(right, left);
+    let (vec, reused) = merged.into_vec_reporting();
+    assert!(vec.is_empty());
+    assert!(reused);
+
+    let (left, right) = Vec::<u32>::new().split_inplace_at(0);
+    assert_eq!(VecShard::joined_slice(&left, &right), Some(&[][..]));
+    let vec: Vec<u32> = left.into();
+    assert_eq!(vec.capacity(), 0);
+    drop(right);
+
+    let mut shard = VecShard::<u8>::new();
+    shard.push(1);
+    assert_eq!(*shard, [1]);
+}
+
+#[test]
+fn cow_shards() {
+    use vecshard::ShardCow;
+
+    let data = [1, 2, 3];
+    let borrowed = ShardCow::from(&data[..]);
+    assert!(borrowed.is_borrowed());
+    assert_eq!(*borrowed, [1, 2, 3]);
+    assert_eq!(borrowed.as_ptr(), data.as_ptr());
+
+    let owned = borrowed.into_owned();
+    assert_ne!(owned.as_ptr(), data.as_ptr());
+    assert_eq!(*owned, [1, 2, 3]);
+
+    let ptr = owned.as_ptr();
+    let cow = ShardCow::from(owned);
+    assert!(cow.is_owned());
+    assert_eq!(cow, ShardCow::Borrowed(&data));
+    // already owned, so nothing gets cloned
+    assert_eq!(cow.into_owned().as_ptr(), ptr);
+}
+
+#[test]
+fn disjoint_mut_refs() {
+    use std::slice::GetDisjointMutError;
+
+    let (_, mut shard) = vec![0, 1, 2, 3, 4].split_inplace_at(2);
+
+    let [a, b, c] = shard.get_disjoint_mut([2, 0, 1]).unwrap();
+    *a += 10;
+    *b += 20;
+    *c += 30;
+    assert_eq!(*shard, [22, 33, 14]);
+
+    assert_eq!(
+        shard.get_disjoint_mut([0, 2, 0]).unwrap_err(),
+        GetDisjointMutError::OverlappingIndices
+    );
+    // indices count from the front of the shard, not the original Vec
+    assert_eq!(
+        shard.get_disjoint_mut([0, 3]).unwrap_err(),
+        GetDisjointMutError::IndexOutOfBounds
+    );
+}
+
+#[test]
+fn global_indices() {
+    let (left, right) = (0..20).collect::<Vec<_>>().split_inplace_at(10);
+
+    for (i, &x) in right.enumerate_global() {
+        assert_eq!(i, x);
+    }
+    assert_eq!(right.enumerate_global().next(), Some((10, &10)));
+    assert_eq!(left.enumerate_global().last(), Some((9, &9)));
+
+    let (_, mut tail) = right.split_inplace_at(7);
+    for (i, x) in tail.enumerate_global_mut() {
+        *x = i * 100;
+    }
+    assert_eq!(*tail, [1700, 1800, 1900]);
+
+    // no way to tell for ZSTs
+    let (_, zsts) = vec![(); 5].split_inplace_at(3);
+    let indices: Vec<_> = zsts.enumerate_global().map(|(i, _)| i).collect();
+    assert_eq!(indices, [0, 1]);
+}
+
+#[test]
+fn splices_like_vec() {
+    let words: Vec<String> = "a b c d e f".split(' ').map(String::from).collect();
+    let new: Vec<String> = "x y z".split(' ').map(String::from).collect();
+
+    for &(start, end) in &[(0, 0), (0, 2), (1, 3), (2, 2), (1, 4), (0, 4), (4, 4)] {
+        for count in 0..=3 {
+            let mut expected = words[1..5].to_vec();
+            let expected_removed: Vec<_> = expected
+                .splice(start..end, new[..count].iter().cloned())
+                .collect();
+
+            // keep siblings around on both sides
+            let (front, rest) = words.clone().split_inplace_at(1);
+            let (mut shard, back) = rest.split_inplace_at(4);
+            let ptr = shard.as_ptr();
+
+            let removed: Vec<_> = shard.splice(start..end, new[..count].iter().cloned()).collect();
+            assert_eq!(removed, expected_removed);
+            assert_eq!(*shard, *expected);
+            if count <= end - start {
+                assert_eq!(shard.as_ptr(), ptr);
+                assert_eq!(shard.capacity(), 4);
+            }
+
+            assert_eq!(*front, ["a"]);
+            assert_eq!(*back, ["f"]);
+        }
+    }
+
+    // all the range flavours
+    let mut shard = VecShard::from(vec![1, 2, 3, 4, 5]);
+    assert_eq!(shard.splice(..2, None).collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(shard.splice(1.., vec![6]).collect::<Vec<_>>(), [4, 5]);
+    assert_eq!(shard.splice(..=0, vec![7, 8]).collect::<Vec<_>>(), [3]);
+    assert_eq!(shard.splice(.., (0..3).filter(|_| true)).collect::<Vec<_>>(), [7, 8, 6]);
+    assert_eq!(*shard, [0, 1, 2]);
+}
+
+#[test]
+fn splice_with_panicking_replacement() {
+    use std::{panic, rc::Rc};
+
+    let rc = Rc::new(());
+    let mut shard = VecShard::from(vec![rc.clone(); 6]);
+
+    let res = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        let replacement = (0..3).map(|i| if i < 1 { rc.clone() } else { panic!("nope") });
+        shard.splice(1..4, replacement);
+    }));
+    assert!(res.is_err());
+
+    // the removed ones are dropped and the one in front is still there,
+    // but the replacement that made it in and the two behind the range are leaked
+    assert_eq!(shard.len(), 1);
+    drop(shard);
+    assert_eq!(Rc::strong_count(&rc), 1 + 1 + 2);
+}
+
+#[test]
+#[should_panic]
+fn splice_out_of_bounds() {
+    let (mut left, _right) = vec![1, 2, 3].split_inplace_at(2);
+    left.splice(1..3, None);
+}
+
 #[test]
 fn backwards_iteration() {
     let mut shard = VecShard::from(vec![0, 1, 2, 3, 4]);
@@ -241,6 +2219,32 @@ fn backwards_iteration() {
     assert_eq!(shard.next_back(), None);
 }
 
+#[test]
+fn take_and_skip() {
+    let mut shard = VecShard::from(vec![1, 2, 3, 4, 5, 6, 7]);
+
+    let front = shard.take_shard(3);
+    assert_eq!(*front, [1, 2, 3]);
+    assert_eq!(*shard, [4, 5, 6, 7]);
+
+    // the two of them still partition the original vec
+    let all = VecShard::merge_inplace(front, shard).unwrap();
+    assert_eq!(*all, [1, 2, 3, 4, 5, 6, 7]);
+
+    let mut shard = all;
+    shard.skip_shard(2);
+    assert_eq!(*shard, [3, 4, 5, 6, 7]);
+
+    // n gets clamped
+    let rest = shard.take_shard(100);
+    assert_eq!(*rest, [3, 4, 5, 6, 7]);
+    assert_eq!(shard.len(), 0);
+
+    let mut rest = rest;
+    rest.skip_shard(100);
+    assert_eq!(rest.len(), 0);
+}
+
 #[test]
 fn lens_match_up() {
     let s1 = VecShard::from(vec![]);
@@ -258,6 +2262,7 @@ fn lens_match_up() {
 }
 
 #[test]
+#[allow(clippy::explicit_auto_deref, clippy::unnecessary_mut_passed)]
 fn borrow_schmorrow() {
     use std::borrow::{Borrow, BorrowMut};
 
@@ -288,3 +2293,341 @@ fn zst_elements() {
     let all = VecShard::merge_inplace(left, right).unwrap();
     assert_eq!(*all, [(); 28]);
 }
+
+#[test]
+fn boxed_closures() {
+    use std::rc::Rc;
+
+    let rc = Rc::new(());
+    let make = |i: usize| {
+        let rc = rc.clone();
+        Box::new(move || {
+            let _ = &rc;
+            i
+        }) as Box<dyn Fn() -> usize>
+    };
+    let call_all = |fns: &[Box<dyn Fn() -> usize>]| fns.iter().map(|f| f()).collect::<Vec<_>>();
+
+    // adjacent, in place
+    let (left, right) = (0..6).map(make).collect::<Vec<_>>().split_inplace_at(2);
+    let all = VecShard::merge(left, right);
+    assert_eq!(call_all(&all), [0, 1, 2, 3, 4, 5]);
+
+    // reverse order, rotated
+    let (left, right) = all.split_inplace_at(4);
+    let mut all = VecShard::merge(right, left);
+    assert_eq!(call_all(&all), [4, 5, 0, 1, 2, 3]);
+
+    // drain from both ends
+    assert_eq!(all.next().unwrap()(), 4);
+    assert_eq!(all.next_back().unwrap()(), 3);
+    assert_eq!(Rc::strong_count(&rc), 5);
+
+    // not adjacent, moved within the allocation
+    let (left, rest) = all.split_inplace_at(1);
+    let (middle, right) = rest.split_inplace_at(1);
+    drop(middle);
+    let all = VecShard::merge(right, left);
+    assert_eq!(call_all(&all), [1, 2, 5]);
+
+    // with a guard in the way, copied into a new Vec
+    let (left, rest) = all.split_inplace_at(1);
+    let (guard, right) = rest.split_inplace_at(1);
+    let all = VecShard::merge(right, left);
+    assert_eq!(call_all(&all), [5, 1]);
+    assert_eq!(guard[0](), 2);
+
+    drop(all);
+    drop(guard);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn boxed_any() {
+    use std::any::Any;
+
+    let items: Vec<Box<dyn Any>> = vec![
+        Box::new(1u8),
+        Box::new(String::from("two")),
+        Box::new([3u64; 3]),
+        Box::new(vec![4.0f32]),
+        Box::new(()),
+    ];
+    let (left, rest) = items.split_inplace_at(2);
+    let (middle, right) = rest.split_inplace_at(1);
+
+    assert_eq!(middle[0].downcast_ref::<[u64; 3]>(), Some(&[3, 3, 3]));
+    drop(middle);
+
+    let mut all = VecShard::merge(right, left);
+    assert_eq!(all.len(), 4);
+    assert_eq!(all[0].downcast_ref::<Vec<f32>>(), Some(&vec![4.0]));
+    assert!(all[1].is::<()>());
+    assert_eq!(all[2].downcast_ref::<u8>(), Some(&1));
+
+    let last = all.next_back().unwrap();
+    assert_eq!(*last.downcast::<String>().unwrap(), "two");
+
+    let vec: Vec<_> = all.into();
+    assert_eq!(vec.len(), 3);
+}
+
+#[test]
+fn covariant_in_t() {
+    // this only compiles if VecShard<T> is covariant in T
+    fn shorten<'a, T>(shard: VecShard<&'static T>) -> VecShard<&'a T> {
+        shard
+    }
+
+    static ANSWER: u32 = 42;
+    let local = 23;
+
+    let (statics, _) = vec![&ANSWER; 3].split_inplace_at(2);
+    let locals = VecShard::from(vec![&local]);
+
+    let all = VecShard::merge(shorten(statics), locals);
+    assert_eq!(*all, [&42, &42, &23]);
+}
+
+#[test]
+fn layout_stays_small() {
+    use std::mem::{align_of, size_of};
+
+    // the allocation's refcount, the data pointer, the length and the spare capacity
+    assert_eq!(size_of::<VecShard<u8>>(), 4 * size_of::<usize>());
+    assert_eq!(size_of::<VecShard<u64>>(), size_of::<VecShard<()>>());
+    assert_eq!(align_of::<VecShard<u8>>(), align_of::<usize>());
+    // the non-null pointers leave room for a niche
+    assert_eq!(size_of::<Option<VecShard<u8>>>(), size_of::<VecShard<u8>>());
+}
+
+#[test]
+#[cfg(not(feature = "wasm-single-thread"))]
+fn send_and_sync() {
+    use std::cell::Cell;
+    use std::sync::Mutex;
+
+    fn assert_send<T: Send>() {}
+    fn assert_sync<T: Sync>() {}
+
+    assert_send::<VecShard<u32>>();
+    assert_sync::<VecShard<u32>>();
+    // Cell is Send, but not Sync
+    assert_send::<VecShard<Cell<u32>>>();
+    // MutexGuard is Sync, but not Send
+    assert_sync::<VecShard<std::sync::MutexGuard<'static, u32>>>();
+
+    // send one half to another thread while the other one stays here
+    let data = vec![Mutex::new(1), Mutex::new(2), Mutex::new(3), Mutex::new(4)];
+    let (here, there) = data.split_inplace_at(2);
+
+    let handle = std::thread::spawn(move || {
+        for m in &*there {
+            *m.lock().unwrap() *= 10;
+        }
+        there
+    });
+    for m in &*here {
+        *m.lock().unwrap() += 1;
+    }
+    let there = handle.join().unwrap();
+
+    let all = VecShard::merge_inplace(here, there).unwrap();
+    let all: Vec<_> = all.map(|m| m.into_inner().unwrap()).collect();
+    assert_eq!(all, [2, 3, 30, 40]);
+}
+
+#[test]
+fn raw_pointers_stay_valid() {
+    let (left, right) = vec![1, 2, 3, 4].split_inplace_at(2);
+    let ptr = right.as_ptr();
+
+    // splitting, merging in place and dropping siblings doesn't move anything
+    std::mem::drop(left);
+    let (middle, last) = right.split_inplace_at(1);
+    assert_eq!(unsafe { *ptr }, 3);
+    let right = VecShard::merge_inplace(middle, last).unwrap();
+    assert_eq!(right.as_ptr(), ptr);
+    assert_eq!(unsafe { *ptr.add(1) }, 4);
+}
+
+#[test]
+fn pushes() {
+    let mut vec = Vec::with_capacity(8);
+    vec.push(0);
+    let mut shard = VecShard::from(vec);
+    let ptr = shard.as_ptr();
+
+    for i in 1..8 {
+        shard.push(i);
+        assert_eq!(shard.as_ptr(), ptr);
+        assert_eq!(shard.capacity(), 8);
+    }
+    // now the capacity is used up and we have to reallocate
+    shard.push(8);
+    assert!(shard.capacity() > 8);
+    assert_eq!(*shard, [0, 1, 2, 3, 4, 5, 6, 7, 8]);
+}
+
+#[test]
+fn capacity_moves_to_the_right() {
+    let mut vec = Vec::with_capacity(10);
+    vec.extend_from_slice(&[1, 2, 3, 4]);
+    let ptr = vec.as_ptr();
+
+    let (mut left, mut right) = vec.split_inplace_at(2);
+    assert_eq!(left.capacity(), 2);
+    assert_eq!(right.capacity(), 8);
+
+    // the right shard can grow in place
+    right.push(5);
+    assert_eq!(unsafe { right.as_ptr().offset_from(ptr) }, 2);
+
+    // the left one can't, it would overwrite the right one
+    left.push(3);
+    assert_eq!(*left, [1, 2, 3]);
+    assert_eq!(*right, [3, 4, 5]);
+    assert!(left.as_ptr() != ptr);
+
+    // merging back in place keeps the rest of the capacity
+    let (a, b) = right.split_inplace_at(1);
+    let mut merged = VecShard::merge_inplace(a, b).unwrap();
+    assert_eq!(merged.capacity(), 8);
+
+    // draining from the back frees up space
+    merged.next_back();
+    assert_eq!(merged.capacity(), 8);
+    merged.next();
+    assert_eq!(merged.capacity(), 7);
+}
+
+#[test]
+fn reclaimed_capacity() {
+    let mut vec = Vec::with_capacity(8);
+    vec.extend(vec!["a", "b", "c", "d", "e"]);
+    let base = vec.as_ptr();
+
+    let (left, rest) = vec.split_inplace_at(2);
+    let (mut middle, right) = rest.split_inplace_at(2);
+
+    // siblings still around, nothing to reclaim
+    middle.reclaim_capacity();
+    assert_eq!(middle.capacity(), 2);
+
+    drop(left);
+    drop(right);
+    middle.reclaim_capacity();
+    assert_eq!(*middle, ["c", "d"]);
+    assert_eq!(middle.as_ptr(), base);
+    assert_eq!(middle.capacity(), 8);
+
+    // pushing doesn't need a new allocation now
+    for s in &["x", "y", "z"] {
+        middle.push(s);
+    }
+    let vec: Vec<_> = middle.into();
+    assert_eq!(vec, ["c", "d", "x", "y", "z"]);
+    assert_eq!(vec.as_ptr(), base);
+    assert_eq!(vec.capacity(), 8);
+}
+
+#[test]
+fn reserving_on_shared_shards() {
+    let (mut left, right) = vec![1u32, 2, 3, 4, 5, 6].split_inplace_at(4);
+    let old_ptr = left.as_ptr();
+
+    // grows geometrically, like a Vec would
+    left.reserve(1);
+    assert_eq!(*left, [1, 2, 3, 4]);
+    assert_eq!(left.capacity(), 8);
+    assert_ne!(left.as_ptr(), old_ptr);
+
+    // unless more than that is asked for
+    let (mut shared, other) = left.split_inplace_at(3);
+    shared.reserve(10);
+    assert_eq!(shared.capacity(), 13);
+    assert_eq!(*shared, [1, 2, 3]);
+
+    // pushing one at a time only reallocates every now and then
+    let mut moves = 0;
+    let mut ptr = shared.as_ptr();
+    for i in 0..100 {
+        shared.push(i);
+        if shared.as_ptr() != ptr {
+            moves += 1;
+            ptr = shared.as_ptr();
+        }
+    }
+    assert!(moves <= 4);
+    assert_eq!(shared.len(), 103);
+    assert_eq!(*other, [4]);
+    assert_eq!(*right, [5, 6]);
+}
+
+#[test]
+fn exact_reservations() {
+    let (mut left, rest) = vec![1u32, 2, 3, 4, 5, 6].split_inplace_at(2);
+    let (mut middle, right) = rest.split_inplace_at(2);
+
+    // a shared shard has to move to grow
+    let old_ptr = left.as_ptr();
+    left.reserve_exact(3);
+    assert_eq!(*left, [1, 2]);
+    assert_eq!(left.capacity(), 5);
+    assert_ne!(left.as_ptr(), old_ptr);
+
+    // enough room already, so nothing happens
+    left.reserve_exact(1);
+    assert_eq!(left.capacity(), 5);
+
+    // middle still shares its allocation with right
+    let old_ptr = middle.as_ptr();
+    let err = middle.try_reserve_exact(usize::MAX);
+    assert!(err.is_err());
+    // a failed reservation leaves a shared shard right where it was
+    assert_eq!(*middle, [3, 4]);
+    assert_eq!(middle.as_ptr(), old_ptr);
+
+    middle.try_reserve_exact(7).unwrap();
+    assert_eq!(*middle, [3, 4]);
+    assert_eq!(middle.capacity(), 9);
+    assert_ne!(middle.as_ptr(), old_ptr);
+    assert_eq!(*right, [5, 6]);
+
+    // a unique shard grows its own allocation, but no further than asked
+    let mut shard = VecShard::from(vec![1u32, 2, 3]);
+    shard.reserve_exact(1);
+    assert_eq!(shard.capacity(), 4);
+    shard.try_reserve_exact(0).unwrap();
+    assert_eq!(shard.capacity(), 4);
+    assert!(shard.try_reserve_exact(usize::MAX).is_err());
+    assert_eq!(*shard, [1, 2, 3]);
+}
+
+#[test]
+fn capacity_after_merges() {
+    let mut vec = Vec::with_capacity(10);
+    vec.extend_from_slice(&[1, 2, 3, 4, 5, 6]);
+
+    // reverse order, the back shard's capacity carries over
+    let (left, right) = vec.split_inplace_at(3);
+    let mut merged = VecShard::merge(right, left);
+    assert_eq!(*merged, [4, 5, 6, 1, 2, 3]);
+    assert_eq!(merged.capacity(), 10);
+
+    // with a hole in between, the merged shard gets everything behind it
+    let (left, rest) = merged.split_inplace_at(2);
+    let (hole, right) = rest.split_inplace_at(2);
+    std::mem::drop(hole);
+    merged = VecShard::merge(left, right);
+    assert_eq!(*merged, [4, 5, 2, 3]);
+    assert_eq!(merged.capacity(), 10);
+
+    let (left, rest) = merged.split_inplace_at(1);
+    let (hole, right) = rest.split_inplace_at(1);
+    std::mem::drop(hole);
+    merged = VecShard::merge(right, left);
+    assert_eq!(*merged, [2, 3, 4]);
+    // this one ends up one slot from the front, so that slot is lost
+    assert_eq!(merged.capacity(), 9);
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "debug-tracking")]
+
+use std::{mem, panic, ptr};
+use vecshard::{ShardExt, VecShard};
+
+#[test]
+fn double_drops_are_caught() {
+    let (left, right) = vec![1, 2, 3, 4].split_inplace_at(2);
+    // Forge a second owner of left's elements, which no safe API can do
+    let forged = unsafe { ptr::read(&left) };
+    // The forged shard doesn't hold its own reference to the allocation, so keep one around for it
+    mem::forget(right);
+
+    drop(left);
+    let res = panic::catch_unwind(panic::AssertUnwindSafe(|| drop(forged)));
+    assert!(res.is_err());
+}
+
+#[test]
+fn reused_slots_are_fine() {
+    // the middle gets dropped and then refilled by the merge
+    let (left, rest) = vec![1, 2, 3, 4, 5].split_inplace_at(2);
+    let (middle, right) = rest.split_inplace_at(1);
+    drop(middle);
+    let merged = VecShard::merge(left, right);
+    assert_eq!(*merged, [1, 2, 4, 5]);
+    drop(merged);
+
+    // same for reclaiming a dropped sibling's space
+    let (left, mut right) = vec![String::from("a"), String::from("b")].split_inplace_at(1);
+    drop(left);
+    right.reclaim_capacity();
+    right.push(String::from("c"));
+    drop(right);
+
+    // draining and batched drops never drop anything twice
+    let mut shard = VecShard::from(vec![String::new(); 10]);
+    shard.next();
+    shard.next_back();
+    shard.push(String::from("d"));
+    shard.drop_in_batches(3).for_each(drop);
+}
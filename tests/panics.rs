@@ -0,0 +1,73 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    panic,
+    sync::{Arc, Mutex},
+};
+use vecshard::{ShardExt, VecShard};
+
+// The panic hook is global, so this is the only test in this file.
+#[test]
+fn panics_point_at_the_caller() {
+    let location = Arc::new(Mutex::new(None));
+    let hook_location = location.clone();
+    panic::set_hook(Box::new(move |info| {
+        let loc = info.location().unwrap();
+        *hook_location.lock().unwrap() = Some((loc.file().to_string(), loc.line()));
+    }));
+
+    let check = |line: u32, f: &mut dyn FnMut()| {
+        let res = panic::catch_unwind(panic::AssertUnwindSafe(f));
+        assert!(res.is_err());
+        let (file, l) = location.lock().unwrap().take().unwrap();
+        assert_eq!((file.as_str(), l), (file!(), line));
+    };
+
+    check(line!() + 1, &mut || {
+        vec![1, 2, 3].split_inplace_at(4);
+    });
+    check(line!() + 1, &mut || {
+        VecShard::from(vec![1, 2, 3]).split_inplace_at(4);
+    });
+    check(line!() + 1, &mut || {
+        VecShard::from(vec![1, 2, 3]).split_where(|_| 10);
+    });
+    check(line!() + 1, &mut || {
+        VecShard::from(vec![1, 2, 3]).split_disjoint(4);
+    });
+    check(line!() + 1, &mut || {
+        VecShard::from(Vec::<u8>::with_capacity(8)).split_with_capacity(1);
+    });
+    check(line!() + 1, &mut || {
+        vec![1, 2, 3].split_inplace_into(0);
+    });
+    check(line!() + 1, &mut || {
+        VecShard::from(vec![1, 2, 3]).into_shard_chunks(0);
+    });
+    check(line!() + 1, &mut || {
+        let _ = VecShard::from(vec![1, 2, 3]).chunks_mut_owned(0);
+    });
+    check(line!() + 1, &mut || {
+        VecShard::from(vec![1, 2, 3]).drop_in_batches(0);
+    });
+    check(line!() + 1, &mut || {
+        VecShard::from(vec![1, 2, 3]).hash_chunks(&mut DefaultHasher::new(), 0);
+    });
+    check(line!() + 1, &mut || {
+        VecShard::from(vec![1, 2, 3]).splice(2..4, None);
+    });
+    check(line!() + 2, &mut || {
+        let shard = VecShard::from(vec![1, 2, 3]);
+        let _ = shard[3];
+    });
+    check(line!() + 1, &mut || {
+        VecShard::from(vec![1, 2, 3]).at(3);
+    });
+    check(line!() + 1, &mut || {
+        VecShard::from(vec![1, 2, 3]).auto_shrink(1.0);
+    });
+    check(line!() + 1, &mut || {
+        VecShard::from(vec![1, 2, 3]).auto_shrink(f32::NAN);
+    });
+
+    let _ = panic::take_hook();
+}
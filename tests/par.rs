@@ -0,0 +1,108 @@
+#![cfg(feature = "rayon")]
+
+use vecshard::{ShardExt, VecShard};
+
+#[test]
+fn par_sorts() {
+    let mut reference: Vec<u64> = (0..100_000u64)
+        .map(|i| (i * 2_654_435_761) % 65_536)
+        .collect();
+    let shard = VecShard::from(reference.clone());
+    let ptr = shard.as_ptr();
+
+    let sorted = shard.par_sort();
+    reference.sort();
+
+    assert_eq!(*sorted, reference[..]);
+    // no reallocations happened along the way
+    assert_eq!(sorted.as_ptr(), ptr);
+    let vec = Vec::from(sorted);
+    assert_eq!(vec.as_ptr(), ptr);
+}
+
+#[test]
+fn par_sort_small_and_split() {
+    assert_eq!(VecShard::from(Vec::<u8>::new()).par_sort().len(), 0);
+
+    let (_, right) = vec![3, 1, 2, 9, 8, 7].split_inplace_at(3);
+    assert_eq!(*right.par_sort(), [7, 8, 9]);
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Keyed(u8, usize);
+
+impl PartialEq for Keyed {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+impl Eq for Keyed {}
+impl PartialOrd for Keyed {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Keyed {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+#[test]
+fn par_sort_is_stable() {
+    let items: Vec<Keyed> = (0..20_000).map(|i| Keyed((i % 7) as u8, i)).collect();
+    let sorted = VecShard::from(items.clone()).par_sort();
+    let mut reference = items;
+    reference.sort();
+
+    let order: Vec<usize> = sorted.iter().map(|k| k.1).collect();
+    let reference_order: Vec<usize> = reference.iter().map(|k| k.1).collect();
+    assert_eq!(order, reference_order);
+}
+
+#[test]
+fn par_folds() {
+    let items: Vec<u64> = (0..100_000u64)
+        .map(|i| (i * 2_654_435_761) % 65_536)
+        .collect();
+    let expected: u64 = items.iter().sum();
+
+    let sum = VecShard::from(items.clone()).par_fold(|| 0, |acc, x| acc + x, |a, b| a + b);
+    assert_eq!(sum, expected);
+
+    // partial results are combined in order
+    let (_, right) = items.split_inplace_at(10);
+    let collected = right.par_fold(
+        Vec::new,
+        |mut acc, x| {
+            acc.push(x);
+            acc
+        },
+        |mut a, b| {
+            a.extend(b);
+            a
+        },
+    );
+    let reference: Vec<u64> = (10..100_000u64)
+        .map(|i| (i * 2_654_435_761) % 65_536)
+        .collect();
+    assert_eq!(collected, reference);
+
+    assert_eq!(
+        VecShard::<u8>::new().par_fold(|| 1, |a, _| a * 2, |a, b| a * b),
+        1
+    );
+}
+
+#[test]
+fn parallel_chunk_mutation() {
+    use rayon::iter::{ParallelBridge, ParallelIterator};
+
+    let mut shard = VecShard::from((0..10_000u32).collect::<Vec<_>>());
+    shard
+        .chunks_mut_owned(256)
+        .par_bridge()
+        .for_each(|chunk| chunk.iter_mut().for_each(|x| *x *= 2));
+
+    assert!(shard.iter().enumerate().all(|(i, &x)| x == 2 * i as u32));
+}
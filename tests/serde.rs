@@ -1,7 +1,8 @@
 #![cfg(feature = "serde")]
 
-use serde_test::{assert_tokens, Token};
-use vecshard::VecShard;
+use serde_test::{assert_de_tokens, assert_ser_tokens, assert_tokens, Token};
+use serde::Deserialize;
+use vecshard::{AsTuple, ShardExt, VecShard};
 
 #[test]
 fn serde_empty() {
@@ -25,3 +26,202 @@ fn serde_chars() {
         ],
     );
 }
+
+#[test]
+fn serde_as_tuple() {
+    let tuple = AsTuple(VecShard::from(vec![1u16, 2, 3]));
+
+    assert_ser_tokens(
+        &tuple,
+        &[
+            Token::Tuple { len: 3 },
+            Token::U16(1),
+            Token::U16(2),
+            Token::U16(3),
+            Token::TupleEnd,
+        ],
+    );
+}
+
+#[test]
+fn as_tuple_bincode_roundtrip() {
+    use bincode::Options;
+    use serde::de::DeserializeSeed;
+
+    let options = bincode::DefaultOptions::new().with_fixint_encoding();
+
+    // two tuples back to back, with something else behind them
+    let first = AsTuple(VecShard::from(vec![1u32, 2, 3]));
+    let second = AsTuple(VecShard::from(vec![4u32, 5]));
+    let mut bytes = options.serialize(&first).unwrap();
+    bytes.extend(options.serialize(&second).unwrap());
+    bytes.extend(options.serialize(&0xFFu8).unwrap());
+    assert_eq!(bytes.len(), 5 * 4 + 1);
+
+    let mut de = bincode::Deserializer::from_slice(&bytes, options);
+    assert_eq!(AsTuple::seed(3).deserialize(&mut de).unwrap(), first);
+    assert_eq!(AsTuple::seed(2).deserialize(&mut de).unwrap(), second);
+    assert_eq!(u8::deserialize(&mut de).unwrap(), 0xFF);
+
+    // an empty tuple takes up no bytes at all
+    let empty = AsTuple(VecShard::<u32>::new());
+    let bytes = options.serialize(&empty).unwrap();
+    assert_eq!(bytes.len(), 0);
+    let mut de = bincode::Deserializer::from_slice(&bytes, options);
+    assert_eq!(AsTuple::seed(0).deserialize(&mut de).unwrap(), empty);
+
+    // asking for more elements than there are fails instead of reading garbage
+    let bytes = options.serialize(&second).unwrap();
+    let mut de = bincode::Deserializer::from_slice(&bytes, options);
+    assert!(AsTuple::<u32>::seed(3).deserialize(&mut de).is_err());
+}
+
+#[test]
+fn serde_split_shards() {
+    let (left, right) = vec![1u8, 2, 3, 4, 5].split_inplace_at(2);
+
+    assert_tokens(
+        &right,
+        &[
+            Token::Seq { len: Some(3) },
+            Token::U8(3),
+            Token::U8(4),
+            Token::U8(5),
+            Token::SeqEnd,
+        ],
+    );
+    assert_tokens(
+        &left,
+        &[
+            Token::Seq { len: Some(2) },
+            Token::U8(1),
+            Token::U8(2),
+            Token::SeqEnd,
+        ],
+    );
+
+    // a shard from the middle, with siblings on both sides
+    let (middle, _rest) = right.split_inplace_at(1);
+    assert_ser_tokens(
+        &AsTuple(middle),
+        &[Token::Tuple { len: 1 }, Token::U8(3), Token::TupleEnd],
+    );
+}
+
+#[test]
+fn serde_layout_bytes() {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use vecshard::serde_layout;
+
+    #[derive(Debug, PartialEq)]
+    struct Blob(VecShard<u8>);
+
+    impl Serialize for Blob {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            serde_layout::serialize(&self.0, s)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Blob {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            serde_layout::deserialize(d).map(Blob)
+        }
+    }
+
+    let blob = Blob(VecShard::from(vec![1, 2, 3]));
+    assert_tokens(&blob, &[Token::Bytes(&[1, 2, 3])]);
+    assert_de_tokens(&blob, &[Token::ByteBuf(&[1, 2, 3])]);
+    assert_de_tokens(
+        &blob,
+        &[
+            Token::Seq { len: Some(3) },
+            Token::U8(1),
+            Token::U8(2),
+            Token::U8(3),
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[test]
+fn serde_layout_round_trips() {
+    use vecshard::serde_layout::{decode, encode, LengthPrefix};
+
+    for &prefix in &[LengthPrefix::U32, LengthPrefix::U64] {
+        let mut buf = encode(b"hello", prefix).unwrap();
+        buf.extend(encode(b"", prefix).unwrap());
+        buf.extend(encode(b"world", prefix).unwrap());
+        assert_eq!(buf.len(), 3 * prefix.width() + 10);
+        let ptr = buf.as_ptr();
+
+        let (hello, rest) = decode(VecShard::from(buf), prefix, 16).unwrap();
+        assert_eq!(*hello, *b"hello");
+        assert_eq!(hello.as_ptr(), ptr.wrapping_add(prefix.width()));
+
+        let (empty, rest) = decode(rest, prefix, 16).unwrap();
+        assert_eq!(empty.len(), 0);
+
+        let (world, rest) = decode(rest, prefix, 16).unwrap();
+        assert_eq!(*world, *b"world");
+        assert_eq!(rest.len(), 0);
+    }
+
+    let buf = encode(&[7; 300], LengthPrefix::U64).unwrap();
+    assert_eq!(buf[..8], 300u64.to_le_bytes());
+}
+
+#[test]
+fn serde_layout_errors() {
+    use vecshard::serde_layout::{decode, LayoutError, LengthPrefix};
+
+    // header cut short
+    let (err, buf) = decode(VecShard::from(vec![5, 0]), LengthPrefix::U32, 16).unwrap_err();
+    assert_eq!(
+        err,
+        LayoutError::Truncated {
+            needed: 4,
+            available: 2
+        }
+    );
+    assert_eq!(*buf, [5, 0]);
+
+    // payload cut short
+    let (err, buf) = decode(
+        VecShard::from(vec![5, 0, 0, 0, 1, 2]),
+        LengthPrefix::U32,
+        16,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        LayoutError::Truncated {
+            needed: 9,
+            available: 6
+        }
+    );
+    assert_eq!(*buf, [5, 0, 0, 0, 1, 2]);
+
+    // length over the limit
+    let (err, _) = decode(VecShard::from(vec![17, 0, 0, 0]), LengthPrefix::U32, 16).unwrap_err();
+    assert_eq!(err, LayoutError::TooLong { len: 17, max: 16 });
+
+    // length field that would overflow if taken at face value
+    let mut huge = u64::MAX.to_le_bytes().to_vec();
+    huge.push(0);
+    let (err, buf) = decode(VecShard::from(huge), LengthPrefix::U64, usize::MAX).unwrap_err();
+    assert_eq!(
+        err,
+        LayoutError::Truncated {
+            needed: u64::MAX,
+            available: 9
+        }
+    );
+    assert_eq!(buf.len(), 9);
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "the blob is truncated: needed {} bytes, but only 9 are available.",
+            u64::MAX
+        )
+    );
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "wasm-single-thread")]
+
+use std::rc::Rc;
+use vecshard::{ShardExt, VecShard};
+
+#[test]
+fn split_and_merge() {
+    let vec: Vec<_> = (0..6).map(Rc::new).collect();
+    let ptr = vec.as_ptr();
+
+    let (left, right) = vec.split_inplace_at(2);
+    let (middle, right) = right.split_inplace_at(2);
+    assert_eq!(*middle, [Rc::new(2), Rc::new(3)]);
+
+    let left = VecShard::merge_inplace(left, middle).unwrap();
+    let whole = VecShard::merge_inplace(left, right).unwrap();
+    assert!(whole.is_whole());
+
+    let vec: Vec<_> = whole.into();
+    assert_eq!(vec.as_ptr(), ptr);
+    assert_eq!(vec.iter().map(|rc| **rc).collect::<Vec<_>>(), [0, 1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn reordered_merge() {
+    let (left, right) = vec![1, 2, 3, 4].split_inplace_at(1);
+    drop(left);
+    let (middle, right) = right.split_inplace_at(1);
+
+    let merged = VecShard::merge(right, middle);
+    assert_eq!(*merged, [3, 4, 2]);
+    assert_eq!(Vec::from(merged), [3, 4, 2]);
+}
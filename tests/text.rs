@@ -0,0 +1,255 @@
+use vecshard::{ShardExt, VecShard};
+
+#[test]
+fn valid_str() {
+    let shard = VecShard::from(Vec::from("Grüß Gott"));
+
+    assert_eq!(shard.as_str(), Ok("Grüß Gott"));
+    assert_eq!(unsafe { shard.as_str_unchecked() }, "Grüß Gott");
+}
+
+#[test]
+fn split_mid_codepoint() {
+    // 'ü' takes up bytes 2 and 3
+    let (left, right) = Vec::from("Grüß").split_inplace_at(3);
+
+    assert!(left.as_str().is_err());
+    assert!(right.as_str().is_err());
+
+    let whole = VecShard::merge_inplace(left, right).unwrap();
+    assert_eq!(whole.as_str(), Ok("Grüß"));
+}
+
+#[test]
+fn lossy_decoding() {
+    use std::borrow::Cow;
+
+    let shard = VecShard::from(Vec::from("Grüß"));
+    assert!(matches!(shard.to_string_lossy(), Cow::Borrowed("Grüß")));
+
+    let (left, right) = Vec::from("Grüß").split_inplace_at(3);
+    assert_eq!(left.to_string_lossy(), "Gr\u{FFFD}");
+    assert_eq!(right.to_string_lossy(), "\u{FFFD}ß");
+
+    let garbage = VecShard::from(vec![b'a', 0xFF, 0xC0, b'b']);
+    assert_eq!(garbage.to_string_lossy(), "a\u{FFFD}\u{FFFD}b");
+}
+
+#[test]
+fn utf16() {
+    let utf16: Vec<u16> = "Servus 👋".encode_utf16().collect();
+    let shard = VecShard::from_utf16(&utf16).unwrap();
+    assert_eq!(shard.as_str(), Ok("Servus 👋"));
+
+    // a lone high surrogate in the middle
+    let broken = [b'h' as u16, 0xD83D, b'i' as u16];
+    assert!(VecShard::from_utf16(&broken).is_err());
+    assert_eq!(VecShard::from_utf16_lossy(&broken).as_str(), Ok("h\u{FFFD}i"));
+
+    assert_eq!(VecShard::from_utf16(&[]).unwrap().len(), 0);
+}
+
+#[test]
+fn reading_into_shards() {
+    use std::io::{self, Cursor, Read};
+
+    let mut cursor = Cursor::new(Vec::from("header\nbody"));
+    let shard = VecShard::from_reader(&mut cursor).unwrap();
+    assert_eq!(shard.as_str(), Ok("header\nbody"));
+    assert_eq!(cursor.position(), 11);
+
+    // the reader is drained, so there's nothing left to read
+    assert_eq!(VecShard::from_reader(&mut cursor).unwrap().len(), 0);
+
+    // more than fits into a single read
+    let big: Vec<u8> = (0..100_000u32).map(|i| i as u8).collect();
+    let shard = VecShard::from_reader(&mut Cursor::new(&big)).unwrap();
+    assert_eq!(*shard, *big);
+
+    // errors are passed on
+    struct Broken;
+    impl Read for Broken {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("broken pipe"))
+        }
+    }
+    let err = VecShard::from_reader(&mut Broken).unwrap_err();
+    assert_eq!(err.to_string(), "broken pipe");
+}
+
+#[test]
+fn string_roundtrip() {
+    let string = String::from("Servus, Schwiegermutter");
+    let old_ptr = string.as_ptr();
+
+    let shard = VecShard::from(string);
+    assert_eq!(shard.as_ptr(), old_ptr);
+
+    // the shard is unique, so this doesn't need to copy
+    let string = shard.try_into_string().unwrap();
+    assert_eq!(string, "Servus, Schwiegermutter");
+    assert_eq!(string.as_ptr(), old_ptr);
+
+    // while the other half is still around, we have to copy
+    let (servus, _rest) = VecShard::from(string).split_inplace_at(6);
+    assert_eq!(servus.try_into_string().unwrap(), "Servus");
+}
+
+#[test]
+fn invalid_string() {
+    use vecshard::error::ConversionFailure;
+
+    let (left, _right) = Vec::from("Grüß").split_inplace_at(3);
+
+    let err = left.try_into_string().unwrap_err();
+    match err.reason {
+        ConversionFailure::InvalidUtf8(utf8_err) => assert_eq!(utf8_err.valid_up_to(), 2),
+        reason => panic!("unexpected reason {:?}", reason),
+    }
+    assert_eq!(
+        err.to_string(),
+        "Can't convert shard because the shard isn't valid UTF-8: incomplete utf-8 byte sequence from index 2."
+    );
+    assert_eq!(*err.shard, [b'G', b'r', 0xc3]);
+}
+
+#[test]
+fn adjacent_text_concat() {
+    let text = VecShard::from(String::from("the quick brown fox"));
+    let old_ptr = text.as_ptr();
+
+    let (the_quick, rest) = text.split_inplace_at(10);
+    let (brown, fox) = rest.split_inplace_at(6);
+
+    // adjacent pieces of the same string merge in place, so the text never moves
+    let brown_fox = VecShard::merge_inplace(brown, fox).unwrap();
+    let text = VecShard::merge_inplace(the_quick, brown_fox).unwrap();
+
+    assert_eq!(text.as_ptr(), old_ptr);
+    assert_eq!(text.as_str(), Ok("the quick brown fox"));
+}
+
+#[test]
+fn split_lines() {
+    let text = VecShard::from(Vec::from("eins\nzwei\n\ndrei\nvier"));
+
+    let lines = text.clone().splitn_owned(10, |&b| b == b'\n');
+    let lines: Vec<_> = lines.iter().map(|l| l.as_str().unwrap()).collect();
+    assert_eq!(lines, ["eins", "zwei", "", "drei", "vier"]);
+
+    let lines = text.clone().splitn_owned(3, |&b| b == b'\n');
+    let lines: Vec<_> = lines.iter().map(|l| l.as_str().unwrap()).collect();
+    assert_eq!(lines, ["eins", "zwei", "\ndrei\nvier"]);
+
+    assert!(text.splitn_owned(0, |&b| b == b'\n').is_empty());
+}
+
+#[test]
+fn split_lines_from_the_back() {
+    let as_strs = |pieces: &[VecShard<u8>]| -> Vec<String> {
+        pieces.iter().map(|p| p.as_str().unwrap().to_owned()).collect()
+    };
+
+    let text = VecShard::from(Vec::from("eins\nzwei\n\ndrei"));
+    let ptr = text.as_ptr();
+    let lines = text.rsplit_owned(|&b| b == b'\n');
+    assert_eq!(as_strs(&lines), ["drei", "", "zwei", "eins"]);
+    assert_eq!(lines[3].as_ptr(), ptr);
+
+    // a trailing delimiter leaves an empty piece at the end, which comes first
+    let lines = VecShard::from(Vec::from("eins\nzwei\n")).rsplit_owned(|&b| b == b'\n');
+    assert_eq!(as_strs(&lines), ["", "zwei", "eins"]);
+
+    // without any delimiters, it's all one piece
+    let lines = VecShard::from(Vec::from("eins")).rsplit_owned(|&b| b == b'\n');
+    assert_eq!(as_strs(&lines), ["eins"]);
+
+    // and the same goes for empty shards, just like with slices
+    let lines = VecShard::from(Vec::new()).rsplit_owned(|&b| b == b'\n');
+    assert_eq!(as_strs(&lines), [""]);
+
+    let expected: Vec<_> = "a,,b,".rsplit(',').collect();
+    let pieces = VecShard::from(Vec::from("a,,b,")).rsplit_owned(|&b| b == b',');
+    assert_eq!(as_strs(&pieces), expected);
+}
+
+#[test]
+fn separators_get_dropped() {
+    use std::rc::Rc;
+
+    let rc = Rc::new(());
+    let shard = VecShard::from(vec![Some(rc.clone()), None, Some(rc.clone()), None]);
+
+    let pieces = shard.splitn_owned(5, |x| x.is_none());
+    assert_eq!(pieces.len(), 3);
+    assert_eq!(Rc::strong_count(&rc), 3);
+
+    std::mem::drop(pieces);
+
+    let shard = VecShard::from(vec![None, Some(rc.clone()), None, Some(rc.clone()), None]);
+    let pieces = shard.rsplit_owned(|x| x.is_none());
+    assert_eq!(pieces.len(), 4);
+    assert_eq!(Rc::strong_count(&rc), 3);
+
+    std::mem::drop(pieces);
+    Rc::try_unwrap(rc).unwrap();
+}
+
+fn read_u8_len(header: &[u8]) -> usize {
+    header[0] as usize
+}
+
+#[test]
+fn frame_splitting() {
+    // complete frame with more after it
+    let buf = VecShard::from(vec![2, 10, 11, 1, 12]);
+    let (frame, rest) = buf.split_frame(1, read_u8_len).unwrap();
+    assert_eq!(*frame, [2, 10, 11]);
+    assert_eq!(*rest, [1, 12]);
+
+    // exactly one frame
+    let (frame, rest) = rest.split_frame(1, read_u8_len).unwrap();
+    assert_eq!(*frame, [1, 12]);
+    assert_eq!(rest.len(), 0);
+
+    // not even a header
+    let rest = rest.split_frame(1, read_u8_len).unwrap_err();
+    assert_eq!(rest.len(), 0);
+
+    // partial payload
+    let buf = VecShard::from(vec![5, 1, 2]);
+    let buf = buf.split_frame(1, read_u8_len).unwrap_err();
+    assert_eq!(*buf, [5, 1, 2]);
+
+    // absurd lengths don't overflow
+    let buf = VecShard::from(vec![0xff; 4]);
+    let buf = buf.split_frame(2, |_| usize::MAX).unwrap_err();
+    assert_eq!(buf.len(), 4);
+
+    // empty header
+    let buf = VecShard::from(vec![1, 2, 3]);
+    let (frame, rest) = buf.split_frame(0, |_| 2).unwrap();
+    assert_eq!((&*frame, &*rest), (&[1, 2][..], &[3][..]));
+}
+
+#[test]
+fn deframing() {
+    let mut buf = Vec::new();
+    for msg in ["moin", "", "servus", "grüezi"].iter() {
+        buf.push(msg.len() as u8);
+        buf.extend_from_slice(msg.as_bytes());
+    }
+    // the start of another message
+    buf.extend_from_slice(&[10, b'h', b'a']);
+
+    let mut frames = VecShard::from(buf).frames(1, read_u8_len);
+    let messages: Vec<_> = frames
+        .by_ref()
+        .map(|frame| String::from_utf8(frame[1..].to_vec()).unwrap())
+        .collect();
+    assert_eq!(messages, ["moin", "", "servus", "grüezi"]);
+
+    assert!(frames.next().is_none());
+    assert_eq!(*frames.remainder(), [10, b'h', b'a']);
+    assert_eq!(*frames.into_remainder(), [10, b'h', b'a']);
+}